@@ -1,3 +1,20 @@
+use std::fs;
+
 fn main() {
-  tauri_build::build()
+  tauri_build::build();
+
+  // The newest migration is the schema version the app knows, it is compared
+  // with the one of the databases it opens.
+  println!("cargo:rerun-if-changed=migrations");
+  let schema_version = fs::read_dir("migrations")
+    .expect("Could not read the migrations")
+    .filter_map(|entry| entry.ok())
+    .filter(|entry| entry.path().is_dir())
+    .filter_map(|entry| {
+      let name = entry.file_name().to_string_lossy().to_string();
+      name.split('_').next().map(|version| version.replace('-', ""))
+    })
+    .max()
+    .expect("There are no migrations");
+  println!("cargo:rustc-env=SCHEMA_VERSION={}", schema_version);
 }
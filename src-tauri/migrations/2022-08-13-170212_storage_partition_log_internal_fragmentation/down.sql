@@ -0,0 +1,2 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE storage_partition_log DROP COLUMN internal_fragmentation
\ No newline at end of file
@@ -0,0 +1,2 @@
+-- Your SQL goes here
+ALTER TABLE storage_partition_log ADD COLUMN internal_fragmentation INTEGER NOT NULL DEFAULT 0
\ No newline at end of file
@@ -0,0 +1,8 @@
+-- Your SQL goes here
+CREATE TABLE page_table (
+    process_id INTEGER NOT NULL,
+    page INTEGER NOT NULL,
+    frame INTEGER NOT NULL,
+    FOREIGN KEY (process_id) REFERENCES process(id),
+    PRIMARY KEY (process_id, page)
+)
\ No newline at end of file
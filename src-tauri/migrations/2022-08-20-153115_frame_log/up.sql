@@ -0,0 +1,9 @@
+-- Your SQL goes here
+CREATE TABLE frame_log (
+    id INTEGER NOT NULL,
+    iteration INTEGER NOT NULL,
+    frame INTEGER NOT NULL,
+    process_id INTEGER,
+    page INTEGER,
+    PRIMARY KEY (id)
+)
\ No newline at end of file
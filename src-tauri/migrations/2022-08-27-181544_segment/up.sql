@@ -0,0 +1,10 @@
+-- Your SQL goes here
+CREATE TABLE segment (
+    id INTEGER NOT NULL,
+    process_id INTEGER NOT NULL,
+    number INTEGER NOT NULL,
+    name VARCHAR NOT NULL,
+    size INTEGER NOT NULL,
+    FOREIGN KEY (process_id) REFERENCES process(id),
+    PRIMARY KEY (id)
+)
\ No newline at end of file
@@ -0,0 +1,10 @@
+-- Your SQL goes here
+CREATE TABLE segment_table (
+    process_id INTEGER NOT NULL,
+    segment INTEGER NOT NULL,
+    storage_partition_id INTEGER NOT NULL,
+    base INTEGER NOT NULL,
+    size INTEGER NOT NULL,
+    FOREIGN KEY (process_id) REFERENCES process(id),
+    PRIMARY KEY (process_id, segment)
+)
\ No newline at end of file
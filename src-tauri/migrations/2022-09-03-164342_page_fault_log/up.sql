@@ -0,0 +1,11 @@
+-- Your SQL goes here
+CREATE TABLE page_fault_log (
+    id INTEGER NOT NULL,
+    iteration INTEGER NOT NULL,
+    process_id INTEGER NOT NULL,
+    page INTEGER NOT NULL,
+    frame INTEGER NOT NULL,
+    victim_page INTEGER,
+    faults INTEGER NOT NULL,
+    PRIMARY KEY (id)
+)
\ No newline at end of file
@@ -0,0 +1,9 @@
+-- Your SQL goes here
+CREATE TABLE swap_log (
+    id INTEGER NOT NULL,
+    iteration INTEGER NOT NULL,
+    process_id INTEGER NOT NULL,
+    storage_partition_id INTEGER NOT NULL,
+    state INTEGER NOT NULL,
+    PRIMARY KEY (id)
+)
\ No newline at end of file
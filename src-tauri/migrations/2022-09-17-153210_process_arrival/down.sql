@@ -0,0 +1,2 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE process DROP COLUMN arrival
\ No newline at end of file
@@ -0,0 +1,2 @@
+-- Your SQL goes here
+ALTER TABLE process ADD COLUMN arrival INTEGER NOT NULL DEFAULT 1
\ No newline at end of file
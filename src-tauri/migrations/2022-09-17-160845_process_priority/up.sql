@@ -0,0 +1,2 @@
+-- Your SQL goes here
+ALTER TABLE process ADD COLUMN priority INTEGER NOT NULL DEFAULT 0
\ No newline at end of file
@@ -0,0 +1,2 @@
+-- Your SQL goes here
+ALTER TABLE process ADD COLUMN io_bursts VARCHAR NOT NULL DEFAULT ''
\ No newline at end of file
@@ -0,0 +1,10 @@
+-- Your SQL goes here
+CREATE TABLE fragmentation_log (
+    iteration INTEGER NOT NULL,
+    free_memory INTEGER NOT NULL,
+    largest_hole INTEGER NOT NULL,
+    holes INTEGER NOT NULL,
+    external_fragmentation DOUBLE NOT NULL,
+    utilization DOUBLE NOT NULL,
+    PRIMARY KEY (iteration)
+)
\ No newline at end of file
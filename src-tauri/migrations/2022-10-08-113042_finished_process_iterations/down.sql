@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE finished_process DROP COLUMN admission_iteration;
+ALTER TABLE finished_process DROP COLUMN first_run_iteration;
+ALTER TABLE finished_process DROP COLUMN finish_iteration;
+ALTER TABLE finished_process DROP COLUMN waiting_time
\ No newline at end of file
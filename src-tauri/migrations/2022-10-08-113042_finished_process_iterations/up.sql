@@ -0,0 +1,5 @@
+-- Your SQL goes here
+ALTER TABLE finished_process ADD COLUMN admission_iteration INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE finished_process ADD COLUMN first_run_iteration INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE finished_process ADD COLUMN finish_iteration INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE finished_process ADD COLUMN waiting_time INTEGER NOT NULL DEFAULT 0
\ No newline at end of file
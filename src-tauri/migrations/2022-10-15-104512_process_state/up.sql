@@ -0,0 +1,9 @@
+-- Your SQL goes here
+CREATE TABLE process_state (
+    process_id INTEGER NOT NULL,
+    time INTEGER NOT NULL,
+    state INTEGER NOT NULL,
+    cpu_time INTEGER NOT NULL,
+    FOREIGN KEY (process_id) REFERENCES process(id),
+    PRIMARY KEY (process_id)
+)
\ No newline at end of file
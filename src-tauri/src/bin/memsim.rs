@@ -0,0 +1,157 @@
+use std::env;
+use std::process::exit;
+
+use app::model::report::{create_report_from_simulator, write_report, SimulationReport};
+use app::model::scenario::{read_scenario, write_scenario};
+use app::model::simulator::Simulator;
+
+const USAGE: &str = "Usage: memsim <scenario file> [--seed <seed>] [--export <scenario file>] \
+                     [--results <report file or directory>]";
+
+/// Runs the scenario in the given file without the window nor the database,
+/// and prints everything the processor did as tab separated tables. The
+/// scenario can also be exported to another file, to change its format, and
+/// the results saved as a JSON report or as CSV files. The seed replaces the
+/// one of the generator of the scenario, to run it with other random processes.
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let path = args.first().unwrap_or_else(|| exit_with_error(USAGE));
+    let options = args[1..].chunks_exact(2);
+    if !options.remainder().is_empty() {
+        exit_with_error(USAGE);
+    }
+    let mut seed = None;
+    let mut export_path = None;
+    let mut results_path = None;
+    for option in options {
+        match option[0].as_str() {
+            "--seed" => {
+                seed = Some(
+                    option[1]
+                        .parse::<u64>()
+                        .unwrap_or_else(|_| exit_with_error(USAGE)),
+                )
+            }
+            "--export" => export_path = Some(&option[1]),
+            "--results" => results_path = Some(&option[1]),
+            _ => exit_with_error(USAGE),
+        }
+    }
+
+    let mut scenario =
+        read_scenario(path).unwrap_or_else(|error| exit_with_error(&error.to_string()));
+    if let Some(seed) = seed {
+        match &mut scenario.generator {
+            Some(generator) => generator.seed = seed,
+            None => exit_with_error("The scenario has no generator to use the seed with"),
+        }
+    }
+    let processes = scenario
+        .create_processes()
+        .unwrap_or_else(|error| exit_with_error(&error.to_string()));
+    if let Some(export_path) = export_path {
+        if let Err(error) = write_scenario(export_path, &scenario) {
+            exit_with_error(&error.to_string());
+        }
+    }
+
+    // The processes that don't fit in the memory would never run.
+    if let Err(error) = scenario.settings.check_processes(&processes) {
+        exit_with_error(&error.to_string());
+    }
+
+    let mut simulator = Simulator::new(&scenario.settings, processes);
+    simulator.run();
+    let log = simulator.take_log();
+    let report = create_report_from_simulator(&scenario.settings, &simulator, &log)
+        .unwrap_or_else(|error| exit_with_error(&error.to_string()));
+
+    print_iterations(&report);
+    print_compactions(&report);
+    print_condensations(&report);
+    print_statistics(&report);
+
+    if let Some(results_path) = results_path {
+        if let Err(error) = write_report(results_path, &report) {
+            exit_with_error(&error.to_string());
+        }
+    }
+}
+
+fn exit_with_error(message: &str) -> ! {
+    eprintln!("{}", message);
+    exit(1);
+}
+
+fn print_iterations(report: &SimulationReport) {
+    println!("# Iterations");
+    println!("iteration\tprocess\tstate\tpartition\ttime_remaining");
+    for process_log in &report.process_logs {
+        println!(
+            "{}\t{}\t{}\t{}\t{}",
+            process_log.iteration,
+            process_log.process_name,
+            process_log.state,
+            process_log.storage_partition_id,
+            process_log.time_remaining
+        );
+    }
+    println!();
+}
+
+fn print_compactions(report: &SimulationReport) {
+    println!("# Compactions");
+    println!("iteration\tpartition\tprevious_position\tfinal_position");
+    for compaction_log in &report.compaction_logs {
+        println!(
+            "{}\t{}\t{}\t{}",
+            compaction_log.iteration,
+            compaction_log.partition,
+            compaction_log.previous_position,
+            compaction_log.final_position
+        );
+    }
+    println!();
+}
+
+fn print_condensations(report: &SimulationReport) {
+    println!("# Condensations");
+    println!("partition\tpartition_size\tnew_partition\tnew_partition_size");
+    for condensation_log in &report.condensation_logs {
+        println!(
+            "{}\t{}\t{}\t{}",
+            condensation_log.partition,
+            condensation_log.partition_size,
+            condensation_log.new_partition,
+            condensation_log.new_partition_size
+        );
+    }
+    println!();
+}
+
+fn print_statistics(report: &SimulationReport) {
+    let statistics = &report.statistics;
+    println!("# Statistics");
+    println!("process\tarrival\tadmission\tfirst_run\tfinish\tturnaround\twaiting\tresponse");
+    for process in &statistics.processes {
+        println!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            process.name,
+            process.arrival,
+            process.admission_iteration,
+            process.first_run_iteration,
+            process.finish_iteration,
+            process.turnaround_time,
+            process.waiting_time,
+            process.response_time
+        );
+    }
+    println!(
+        "average\t\t\t\t\t{:.2}\t{:.2}\t{:.2}",
+        statistics.average_turnaround_time,
+        statistics.average_waiting_time,
+        statistics.average_response_time
+    );
+    println!("compactions\t{}", report.compactions);
+    println!("condensations\t{}", report.condensations);
+}
@@ -1,163 +1,353 @@
+use std::str::FromStr;
+
 use diesel::prelude::*;
+use diesel::sqlite::SqliteConnection;
 
 use super::{establish_connection, models, schema};
 
+use crate::error::SimError;
+use crate::model::allocation::AllocationEnum;
+use crate::model::compaction::CompactionEnum;
 use crate::model::configuration::SettingName;
+use crate::model::placement::PlacementEnum;
+use crate::model::replacement::ReplacementEnum;
+use crate::model::scheduling::SchedulingEnum;
+use crate::model::simulator::SimulatorSettings;
 
-pub fn reset_configuration() -> () {
+pub fn reset_configuration() -> Result<(), SimError> {
     use schema::configuration;
 
-    let connection = establish_connection();
-    diesel::delete(configuration::table)
-        .execute(&connection)
-        .expect("Could not delete table contents");
+    let connection = establish_connection()?;
+    diesel::delete(configuration::table).execute(&connection)?;
+    Ok(())
 }
 
-pub fn increment_compactions() -> () {
-    use schema::configuration;
+// The counters of the simulation are saved with the rest of an iteration, on
+// its connection.
+pub fn set_compactions(connection: &SqliteConnection, compactions: i32) -> Result<(), SimError> {
+    save_configuration_value(
+        connection,
+        SettingName::Compactions,
+        compactions.to_string(),
+    )
+}
 
-    let connection = establish_connection();
-    let compactions = get_compactions() + 1;
+pub fn set_condensations(
+    connection: &SqliteConnection,
+    condensations: i32,
+) -> Result<(), SimError> {
+    save_configuration_value(
+        connection,
+        SettingName::Condensations,
+        condensations.to_string(),
+    )
+}
 
-    diesel::update(configuration::table.find(SettingName::Compactions as i32))
-        .set(configuration::setting_value.eq(compactions.to_string()))
-        .execute(&connection)
-        .expect("Could not increment compactions");
+pub fn get_compactions() -> Result<i32, SimError> {
+    get_setting(SettingName::Compactions, 0)
 }
 
-pub fn increment_condensations() -> () {
-    use schema::configuration;
+pub fn get_condensations() -> Result<i32, SimError> {
+    get_setting(SettingName::Condensations, 0)
+}
 
-    let connection = establish_connection();
-    let condensations = get_condensations() + 1;
+pub fn set_memory_size(size: i32) -> Result<(), SimError> {
+    set_configuration_value(SettingName::MemorySize, size.to_string())
+}
 
-    diesel::update(configuration::table.find(SettingName::Condensations as i32))
-        .set(configuration::setting_value.eq(condensations.to_string()))
-        .execute(&connection)
-        .expect("Could not increment condensations");
+pub fn set_placement_strategy(strategy: i32) -> Result<(), SimError> {
+    set_configuration_value(SettingName::PlacementStrategy, strategy.to_string())
 }
 
-pub fn increment_partition_consecutive_number() -> () {
-    use schema::configuration;
+pub fn get_placement_strategy() -> Result<i32, SimError> {
+    // First-fit is used if the user has not chosen any strategy.
+    get_setting(
+        SettingName::PlacementStrategy,
+        PlacementEnum::FirstFit as i32,
+    )
+}
 
-    let connection = establish_connection();
-    let last_consecutive_number = get_partition_consecutive_number() + 1;
+pub fn set_allocation_mode(mode: i32) -> Result<(), SimError> {
+    set_configuration_value(SettingName::AllocationMode, mode.to_string())
+}
 
-    diesel::update(configuration::table.find(SettingName::PartitionConsecutiveNumber as i32))
-        .set(configuration::setting_value.eq(last_consecutive_number.to_string()))
-        .execute(&connection)
-        .expect("Could not increment partition consecutive number");
+pub fn get_allocation_mode() -> Result<i32, SimError> {
+    // Dynamic partitions are used if the user has not chosen any mode.
+    get_setting(
+        SettingName::AllocationMode,
+        AllocationEnum::DynamicPartitions as i32,
+    )
 }
 
-pub fn get_compactions() -> i32 {
-    use schema::configuration;
+pub fn set_fixed_partition_sizes(sizes: &[i32]) -> Result<(), SimError> {
+    set_configuration_value(
+        SettingName::FixedPartitionSizes,
+        get_fixed_partition_sizes_value(sizes),
+    )
+}
 
-    let connection = establish_connection();
+/// The sizes are saved in order, separated by commas.
+fn get_fixed_partition_sizes_value(sizes: &[i32]) -> String {
+    sizes
+        .iter()
+        .map(|size| size.to_string())
+        .collect::<Vec<String>>()
+        .join(",")
+}
 
-    let compactions = get_configuration_value(SettingName::Compactions);
-    if compactions.is_err() {
-        diesel::insert_into(configuration::table)
-            .values(&models::Configuration {
-                setting_id: SettingName::Compactions as i32,
-                setting_value: 0.to_string(),
-            })
-            .execute(&connection)
-            .expect("Could not create compactions number");
-    }
-    get_configuration_value(SettingName::Compactions)
-        .unwrap()
+pub fn get_fixed_partition_sizes() -> Result<Vec<i32>, SimError> {
+    let connection = establish_connection()?;
+    let configuration =
+        match get_configuration_value(&connection, SettingName::FixedPartitionSizes)? {
+            Some(configuration) => configuration,
+            None => return Ok(Vec::new()),
+        };
+    configuration
         .setting_value
-        .parse::<i32>()
-        .unwrap()
+        .split(',')
+        .filter(|size| !size.is_empty())
+        .map(|size| {
+            size.parse::<i32>().map_err(|_| {
+                SimError::InvalidConfiguration(format!(
+                    "The fixed partition sizes {:?} are not valid",
+                    configuration.setting_value
+                ))
+            })
+        })
+        .collect()
 }
 
-pub fn get_condensations() -> i32 {
-    use schema::configuration;
+pub fn set_page_size(size: i32) -> Result<(), SimError> {
+    set_configuration_value(SettingName::PageSize, size.to_string())
+}
 
-    let connection = establish_connection();
+pub fn get_page_size() -> Result<i32, SimError> {
+    get_setting(SettingName::PageSize, 4)
+}
 
-    let condensations = get_configuration_value(SettingName::Condensations);
+pub fn set_replacement_policy(policy: i32) -> Result<(), SimError> {
+    set_configuration_value(SettingName::ReplacementPolicy, policy.to_string())
+}
 
-    if condensations.is_err() {
-        diesel::insert_into(configuration::table)
-            .values(&models::Configuration {
-                setting_id: SettingName::Condensations as i32,
-                setting_value: 0.to_string(),
-            })
-            .execute(&connection)
-            .expect("Could not create condensation number");
-    }
+pub fn get_replacement_policy() -> Result<i32, SimError> {
+    // FIFO is used if the user has not chosen any policy.
+    get_setting(SettingName::ReplacementPolicy, ReplacementEnum::Fifo as i32)
+}
 
-    get_configuration_value(SettingName::Condensations)
-        .unwrap()
-        .setting_value
-        .parse::<i32>()
-        .unwrap()
+pub fn set_frames_per_process(frames: i32) -> Result<(), SimError> {
+    set_configuration_value(SettingName::FramesPerProcess, frames.to_string())
 }
 
-pub fn get_partition_consecutive_number() -> i32 {
-    use schema::configuration;
+pub fn get_frames_per_process() -> Result<i32, SimError> {
+    get_setting(SettingName::FramesPerProcess, 3)
+}
 
-    let connection = establish_connection();
+pub fn set_swapping(swapping: bool) -> Result<(), SimError> {
+    set_configuration_value(SettingName::Swapping, swapping.to_string())
+}
 
-    let partition_consecutive_number =
-        get_configuration_value(SettingName::PartitionConsecutiveNumber);
+pub fn get_swapping() -> Result<bool, SimError> {
+    get_setting(SettingName::Swapping, false)
+}
 
-    if partition_consecutive_number.is_err() {
-        diesel::insert_into(configuration::table)
-            .values(&models::Configuration {
-                setting_id: SettingName::PartitionConsecutiveNumber as i32,
-                setting_value: 1.to_string(),
-            })
-            .execute(&connection)
-            .expect("Could not create partition consecutive number");
-    }
-    get_configuration_value(SettingName::PartitionConsecutiveNumber)
-        .unwrap()
-        .setting_value
-        .parse::<i32>()
-        .unwrap()
+pub fn set_swap_time(time: i32) -> Result<(), SimError> {
+    set_configuration_value(SettingName::SwapTime, time.to_string())
 }
 
-pub fn set_memory_size(size: i32) -> () {
-    use schema::configuration;
+pub fn get_swap_time() -> Result<i32, SimError> {
+    get_setting(SettingName::SwapTime, 1)
+}
 
-    let connection = establish_connection();
+pub fn set_scheduling_policy(policy: i32) -> Result<(), SimError> {
+    set_configuration_value(SettingName::SchedulingPolicy, policy.to_string())
+}
 
-    // The configuration has not been set yet.
-    if configuration::table
-        .filter(configuration::setting_id.eq(SettingName::MemorySize as i32))
-        .first::<models::Configuration>(&connection)
-        .is_err()
-    {
-        diesel::insert_into(configuration::table)
-            .values(&models::Configuration {
-                setting_id: SettingName::MemorySize as i32,
-                setting_value: size.to_string(),
-            })
-            .execute(&connection)
-            .expect("Could not init configuration.");
-    } else {
-        diesel::update(configuration::table.find(SettingName::MemorySize as i32))
-            .set(configuration::setting_value.eq(size.to_string()))
-            .execute(&connection)
-            .expect("Could not update memory size");
+pub fn get_scheduling_policy() -> Result<i32, SimError> {
+    // Round robin is used if the user has not chosen any policy.
+    get_setting(
+        SettingName::SchedulingPolicy,
+        SchedulingEnum::RoundRobin as i32,
+    )
+}
+
+pub fn set_quantum(quantum: i32) -> Result<(), SimError> {
+    set_configuration_value(SettingName::Quantum, quantum.to_string())
+}
+
+pub fn get_quantum() -> Result<i32, SimError> {
+    get_setting(SettingName::Quantum, 1)
+}
+
+pub fn set_cpus(cpus: i32) -> Result<(), SimError> {
+    set_configuration_value(SettingName::Cpus, cpus.to_string())
+}
+
+pub fn get_cpus() -> Result<i32, SimError> {
+    get_setting(SettingName::Cpus, 0)
+}
+
+pub fn set_aging(aging: i32) -> Result<(), SimError> {
+    set_configuration_value(SettingName::Aging, aging.to_string())
+}
+
+pub fn get_aging() -> Result<i32, SimError> {
+    // The processes don't age if the user has not chosen to.
+    get_setting(SettingName::Aging, 0)
+}
+
+pub fn set_io_devices(devices: i32) -> Result<(), SimError> {
+    set_configuration_value(SettingName::IoDevices, devices.to_string())
+}
+
+pub fn get_io_devices() -> Result<i32, SimError> {
+    get_setting(SettingName::IoDevices, 1)
+}
+
+pub fn set_compaction_policy(policy: i32) -> Result<(), SimError> {
+    set_configuration_value(SettingName::CompactionPolicy, policy.to_string())
+}
+
+pub fn get_compaction_policy() -> Result<i32, SimError> {
+    // The memory is compacted in every iteration if the user has not chosen
+    // any policy.
+    get_setting(SettingName::CompactionPolicy, CompactionEnum::Always as i32)
+}
+
+pub fn set_compaction_threshold(threshold: i32) -> Result<(), SimError> {
+    set_configuration_value(SettingName::CompactionThreshold, threshold.to_string())
+}
+
+pub fn get_compaction_threshold() -> Result<i32, SimError> {
+    get_setting(SettingName::CompactionThreshold, 50)
+}
+
+pub fn get_memory_size() -> Result<i32, SimError> {
+    // The base memory size is used if the user has not changed it.
+    get_setting(SettingName::MemorySize, 50)
+}
+
+pub fn get_simulator_settings() -> Result<SimulatorSettings, SimError> {
+    Ok(SimulatorSettings {
+        memory_size: get_memory_size()?,
+        placement_strategy: get_placement_strategy()?,
+        allocation_mode: get_allocation_mode()?,
+        fixed_partition_sizes: get_fixed_partition_sizes()?,
+        page_size: get_page_size()?,
+        replacement_policy: get_replacement_policy()?,
+        frames_per_process: get_frames_per_process()?,
+        swapping: get_swapping()?,
+        swap_time: get_swap_time()?,
+        scheduling_policy: get_scheduling_policy()?,
+        quantum: get_quantum()?,
+        cpus: get_cpus()?,
+        aging: get_aging()?,
+        io_devices: get_io_devices()?,
+        compaction_policy: get_compaction_policy()?,
+        compaction_threshold: get_compaction_threshold()?,
+    })
+}
+
+/// All the settings are saved on the same connection, so they can be saved
+/// in the same transaction as the processes they are used with.
+pub fn set_simulator_settings(
+    connection: &SqliteConnection,
+    settings: &SimulatorSettings,
+) -> Result<(), SimError> {
+    let values = [
+        (SettingName::MemorySize, settings.memory_size.to_string()),
+        (
+            SettingName::PlacementStrategy,
+            settings.placement_strategy.to_string(),
+        ),
+        (
+            SettingName::AllocationMode,
+            settings.allocation_mode.to_string(),
+        ),
+        (
+            SettingName::FixedPartitionSizes,
+            get_fixed_partition_sizes_value(&settings.fixed_partition_sizes),
+        ),
+        (SettingName::PageSize, settings.page_size.to_string()),
+        (
+            SettingName::ReplacementPolicy,
+            settings.replacement_policy.to_string(),
+        ),
+        (
+            SettingName::FramesPerProcess,
+            settings.frames_per_process.to_string(),
+        ),
+        (SettingName::Swapping, settings.swapping.to_string()),
+        (SettingName::SwapTime, settings.swap_time.to_string()),
+        (
+            SettingName::SchedulingPolicy,
+            settings.scheduling_policy.to_string(),
+        ),
+        (SettingName::Quantum, settings.quantum.to_string()),
+        (SettingName::Cpus, settings.cpus.to_string()),
+        (SettingName::Aging, settings.aging.to_string()),
+        (SettingName::IoDevices, settings.io_devices.to_string()),
+        (
+            SettingName::CompactionPolicy,
+            settings.compaction_policy.to_string(),
+        ),
+        (
+            SettingName::CompactionThreshold,
+            settings.compaction_threshold.to_string(),
+        ),
+    ];
+    for (setting, value) in values {
+        save_configuration_value(connection, setting, value)?;
     }
+    Ok(())
 }
 
-pub fn get_memory_size() -> i32 {
-    return get_configuration_value(SettingName::MemorySize)
-        .unwrap()
-        .setting_value
-        .parse::<i32>()
-        .unwrap();
+/// The value of the setting, or the default if it has not been set yet.
+fn get_setting<T: FromStr>(setting: SettingName, default: T) -> Result<T, SimError> {
+    match get_configuration_value(&establish_connection()?, setting)? {
+        Some(configuration) => configuration.setting_value.parse::<T>().map_err(|_| {
+            SimError::InvalidConfiguration(format!(
+                "The value {:?} of setting {:?} is not valid",
+                configuration.setting_value, setting
+            ))
+        }),
+        None => Ok(default),
+    }
 }
 
-fn get_configuration_value(value: SettingName) -> QueryResult<models::Configuration> {
+fn get_configuration_value(
+    connection: &SqliteConnection,
+    value: SettingName,
+) -> Result<Option<models::Configuration>, SimError> {
     use schema::configuration;
 
-    let connection = establish_connection();
-    configuration::table
+    Ok(configuration::table
         .find(value as i32)
-        .first::<models::Configuration>(&connection)
+        .first::<models::Configuration>(connection)
+        .optional()?)
+}
+
+fn set_configuration_value(setting: SettingName, value: String) -> Result<(), SimError> {
+    save_configuration_value(&establish_connection()?, setting, value)
+}
+
+fn save_configuration_value(
+    connection: &SqliteConnection,
+    setting: SettingName,
+    value: String,
+) -> Result<(), SimError> {
+    use schema::configuration;
+
+    if get_configuration_value(connection, setting)?.is_none() {
+        diesel::insert_into(configuration::table)
+            .values(&models::Configuration {
+                setting_id: setting as i32,
+                setting_value: value,
+            })
+            .execute(connection)?;
+    } else {
+        diesel::update(configuration::table.find(setting as i32))
+            .set(configuration::setting_value.eq(value))
+            .execute(connection)?;
+    }
+    Ok(())
 }
@@ -0,0 +1,202 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use once_cell::sync::Lazy;
+
+use crate::error::SimError;
+
+use super::migration::run_migrations;
+
+/// Where the database was before it could be chosen, relative to the directory
+/// the app runs from.
+const LEGACY_DATABASE_PATH: &str = "../public/data.sqlite";
+const DEFAULT_DATABASE_NAME: &str = "data";
+const DATABASE_EXTENSION: &str = "sqlite";
+
+/// The file every connection is made to, it changes when another simulation
+/// database is opened.
+static DATABASE_PATH: Lazy<RwLock<PathBuf>> =
+    Lazy::new(|| RwLock::new(PathBuf::from(LEGACY_DATABASE_PATH)));
+
+pub fn get_database_path() -> PathBuf {
+    DATABASE_PATH.read().unwrap().clone()
+}
+
+/// Chooses the database when the app starts. It is the path given to the app,
+/// or the one in the DATABASE_URL variable, or the default database in the
+/// data directory of the app. The default database starts as a copy of the one
+/// the app used before, if there is one. The database is migrated, but it is
+/// kept even if it can't be, so the error can be checked again later.
+pub fn init_database_path(
+    argument: Option<PathBuf>,
+    app_dir: Option<PathBuf>,
+) -> Result<(), SimError> {
+    let path = match argument.or_else(|| env::var_os("DATABASE_URL").map(PathBuf::from)) {
+        Some(path) => path,
+        None => {
+            let app_dir = app_dir.ok_or_else(|| {
+                SimError::File("Could not find the data directory of the app".to_string())
+            })?;
+            fs::create_dir_all(&app_dir).map_err(|error| {
+                SimError::File(format!(
+                    "Could not create directory {}: {}",
+                    app_dir.display(),
+                    error
+                ))
+            })?;
+            let path = get_path_with_name(&app_dir, DEFAULT_DATABASE_NAME);
+            let legacy_path = Path::new(LEGACY_DATABASE_PATH);
+            if !path.exists() && legacy_path.exists() {
+                fs::copy(legacy_path, &path).map_err(|error| {
+                    SimError::File(format!(
+                        "Could not copy database {}: {}",
+                        legacy_path.display(),
+                        error
+                    ))
+                })?;
+            }
+            path
+        }
+    };
+    *DATABASE_PATH.write().unwrap() = path;
+    check_database()
+}
+
+/// Brings the open database up to date, the error says why it can't be used.
+pub fn check_database() -> Result<(), SimError> {
+    run_migrations(&get_database_path())
+}
+
+/// The name of the open database, without its extension.
+pub fn get_database_name() -> String {
+    get_database_path()
+        .file_stem()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// The names of the databases in the same directory as the open one.
+pub fn select_database_names() -> Vec<String> {
+    let entries = match fs::read_dir(get_database_directory()) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .map(|extension| extension == DATABASE_EXTENSION)
+                .unwrap_or(false)
+        })
+        .filter_map(|path| {
+            path.file_stem()
+                .map(|name| name.to_string_lossy().to_string())
+        })
+        .collect();
+    names.sort();
+    names
+}
+
+/// Makes the connections go to another database in the directory of the open
+/// one.
+pub fn open_database(name: &str) -> Result<(), SimError> {
+    check_database_name(name)?;
+    let path = get_path_with_name(&get_database_directory(), name);
+    if !path.exists() {
+        return Err(SimError::NotFound(format!("Simulation database {}", name)));
+    }
+    run_migrations(&path)?;
+    *DATABASE_PATH.write().unwrap() = path;
+    Ok(())
+}
+
+/// Creates an empty database next to the open one and opens it.
+pub fn create_database(name: &str) -> Result<(), SimError> {
+    check_database_name(name)?;
+    let path = get_path_with_name(&get_database_directory(), name);
+    if path.exists() {
+        return Err(SimError::ConstraintViolation(format!(
+            "There is already a simulation database named {}",
+            name
+        )));
+    }
+    run_migrations(&path)?;
+    *DATABASE_PATH.write().unwrap() = path;
+    Ok(())
+}
+
+/// A database given without a directory is in the one the app runs from.
+fn get_database_directory() -> PathBuf {
+    match get_database_path().parent() {
+        Some(directory) if directory != Path::new("") => directory.to_path_buf(),
+        _ => PathBuf::from("."),
+    }
+}
+
+fn get_path_with_name(directory: &Path, name: &str) -> PathBuf {
+    directory.join(format!("{}.{}", name, DATABASE_EXTENSION))
+}
+
+/// The name is used as the name of the file, so it can't have separators.
+fn check_database_name(name: &str) -> Result<(), SimError> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(SimError::InvalidConfiguration(format!(
+            "The name {:?} can only have letters, numbers, - and _",
+            name
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_database::open_test_database;
+
+    #[test]
+    fn a_new_database_is_created_next_to_the_open_one() {
+        let (_database, path) = open_test_database("location");
+        let new_path = path.with_file_name("created.sqlite");
+        let _ = fs::remove_file(&new_path);
+
+        create_database("created").unwrap();
+        assert_eq!(get_database_path(), new_path);
+        assert_eq!(get_database_name(), "created");
+        let names = select_database_names();
+        assert!(names.contains(&"location".to_string()));
+        assert!(names.contains(&"created".to_string()));
+
+        let error = create_database("location").err().unwrap();
+        assert_eq!(error.get_kind(), "ConstraintViolation");
+        open_database("location").unwrap();
+        assert_eq!(get_database_path(), path);
+        fs::remove_file(&new_path).unwrap();
+    }
+
+    #[test]
+    fn only_the_databases_that_exist_can_be_opened() {
+        let (_database, path) = open_test_database("location_missing");
+
+        let error = open_database("missing").err().unwrap();
+        assert_eq!(error.get_kind(), "NotFound");
+        assert_eq!(get_database_path(), path);
+    }
+
+    #[test]
+    fn the_names_can_not_leave_the_directory() {
+        let (_database, path) = open_test_database("location_names");
+
+        for name in ["", "../data", "data.sqlite", "a/b"] {
+            let error = create_database(name).err().unwrap();
+            assert_eq!(error.get_kind(), "InvalidConfiguration");
+        }
+        assert_eq!(get_database_path(), path);
+    }
+}
@@ -0,0 +1,47 @@
+use std::path::Path;
+
+use diesel::prelude::*;
+use diesel::sqlite::SqliteConnection;
+use diesel_migrations::{setup_database, MigrationConnection};
+
+use crate::error::SimError;
+
+embed_migrations!();
+
+/// The version of the newest migration, set by the build script.
+const SCHEMA_VERSION: &str = env!("SCHEMA_VERSION");
+
+/// Runs the migrations the database doesn't have yet, an empty database gets
+/// every table. A database migrated by a newer version of the app is left as
+/// it is, as this version doesn't know its tables.
+pub fn run_migrations(path: &Path) -> Result<(), SimError> {
+    let database_url = path.to_string_lossy();
+    let connection = SqliteConnection::establish(&database_url)?;
+    setup_database(&connection).map_err(|error| {
+        SimError::Migration(format!(
+            "Could not read database {}: {}",
+            database_url, error
+        ))
+    })?;
+    let version = connection.latest_run_migration_version().map_err(|error| {
+        SimError::Migration(format!(
+            "Could not read database {}: {}",
+            database_url, error
+        ))
+    })?;
+    if let Some(version) = version {
+        if version.as_str() > SCHEMA_VERSION {
+            return Err(SimError::Migration(format!(
+                "The database {} is from a newer version of the app, its schema version is {} \
+                 and this version only knows up to {}",
+                database_url, version, SCHEMA_VERSION
+            )));
+        }
+    }
+    embedded_migrations::run(&connection).map_err(|error| {
+        SimError::Migration(format!(
+            "Could not update database {}: {}",
+            database_url, error
+        ))
+    })
+}
@@ -1,8 +1,9 @@
 use serde::Serialize;
 
 use super::schema::{
-    compaction_log, condensation_log, configuration, finished_process, process, process_log,
-    process_partition, storage_partition, storage_partition_log,
+    compaction_log, condensation_log, configuration, finished_process, fragmentation_log,
+    frame_log, iteration_log, page_fault_log, page_table, process, process_log, process_partition,
+    process_state, segment, segment_table, storage_partition, storage_partition_log, swap_log,
 };
 
 #[derive(Insertable)]
@@ -10,23 +11,24 @@ use super::schema::{
 pub struct NewFinishedProcess {
     pub process_id: i32,
     pub partition_number: i32,
+    pub admission_iteration: i32,
+    pub first_run_iteration: i32,
+    pub finish_iteration: i32,
+    pub waiting_time: i32,
 }
 #[derive(Queryable, Serialize)]
 pub struct FinishedProcess {
     pub id: i32,
     pub process_id: i32,
     pub partition_number: i32,
+    pub admission_iteration: i32,
+    pub first_run_iteration: i32,
+    pub finish_iteration: i32,
+    pub waiting_time: i32,
 }
 
-#[derive(Insertable)]
+#[derive(Queryable, Serialize, Insertable, Clone)]
 #[table_name = "compaction_log"]
-pub struct NewCompactionLog {
-    pub iteration: i32,
-    pub partition: i32,
-    pub previous_position: i32,
-    pub final_position: i32,
-}
-#[derive(Queryable, Serialize)]
 pub struct CompactionLog {
     pub id: i32,
     pub iteration: i32,
@@ -35,15 +37,8 @@ pub struct CompactionLog {
     pub final_position: i32,
 }
 
-#[derive(Insertable)]
+#[derive(Queryable, Serialize, Insertable, Clone)]
 #[table_name = "condensation_log"]
-pub struct NewCondensationLog {
-    pub partition: i32,
-    pub partition_size: i32,
-    pub new_partition: i32,
-    pub new_partition_size: i32,
-}
-#[derive(Queryable, Serialize)]
 pub struct CondensationLog {
     pub id: i32,
     pub partition: i32,
@@ -59,6 +54,21 @@ pub struct Process {
     pub time: i32,
     pub size: i32,
     pub state: i32,
+    pub reference_string: String,
+    pub arrival: i32,
+    pub priority: i32,
+    pub io_bursts: String,
+}
+
+/// The time left and the state of a process in the current simulation, the
+/// process itself keeps the values it was created with.
+#[derive(Queryable, Serialize, Insertable, Clone)]
+#[table_name = "process_state"]
+pub struct ProcessState {
+    pub process_id: i32,
+    pub time: i32,
+    pub state: i32,
+    pub cpu_time: i32,
 }
 
 #[derive(Insertable)]
@@ -68,9 +78,14 @@ pub struct NewProcess<'a> {
     pub time: i32,
     pub size: i32,
     pub state: i32,
+    pub reference_string: &'a str,
+    pub arrival: i32,
+    pub priority: i32,
+    pub io_bursts: &'a str,
 }
 
-#[derive(Queryable, Serialize)]
+#[derive(Queryable, Serialize, Insertable, Clone)]
+#[table_name = "storage_partition"]
 pub struct StoragePartition {
     pub id: i32,
     pub number: i32,
@@ -80,28 +95,20 @@ pub struct StoragePartition {
     pub size: i32,
 }
 
-#[derive(Insertable)]
-#[table_name = "storage_partition"]
-pub struct NewStoragePartition {
-    pub number: i32,
-    pub position: i32,
-    pub position_start: i32,
-    pub position_end: i32,
-    pub size: i32,
-}
-
-#[derive(Queryable, Serialize)]
+#[derive(Queryable, Serialize, Insertable)]
+#[table_name = "iteration_log"]
 pub struct IterationLog {
     pub id: i32,
 }
 
-#[derive(Insertable)]
+#[derive(Insertable, Serialize, Clone)]
 #[table_name = "storage_partition_log"]
 pub struct NewStoragePartitionLog {
     pub iteration: i32,
     pub storage_partition_id: i32,
     pub position: i32,
     pub size: i32,
+    pub internal_fragmentation: i32,
 }
 
 #[derive(Queryable, Serialize)]
@@ -111,19 +118,11 @@ pub struct StoragePartitionLog {
     pub storage_partition_id: i32,
     pub position: i32,
     pub size: i32,
+    pub internal_fragmentation: i32,
 }
 
-#[derive(Insertable)]
+#[derive(Queryable, Serialize, Insertable, Clone)]
 #[table_name = "process_log"]
-pub struct NewProcessLog {
-    pub iteration: i32,
-    pub process_id: i32,
-    pub storage_partition_id: i32,
-    pub time_remaining: i32,
-    pub state: i32,
-}
-
-#[derive(Queryable, Serialize)]
 pub struct ProcessLog {
     pub id: i32,
     pub iteration: i32,
@@ -133,7 +132,7 @@ pub struct ProcessLog {
     pub state: i32,
 }
 
-#[derive(Queryable, Serialize, Insertable)]
+#[derive(Queryable, Serialize, Insertable, Clone)]
 #[table_name = "process_partition"]
 pub struct ProcessPartition {
     pub process_id: i32,
@@ -146,3 +145,90 @@ pub struct Configuration {
     pub setting_id: i32,
     pub setting_value: String,
 }
+
+#[derive(Queryable, Serialize, Insertable, Clone)]
+#[table_name = "page_table"]
+pub struct PageTable {
+    pub process_id: i32,
+    pub page: i32,
+    pub frame: i32,
+}
+
+#[derive(Insertable)]
+#[table_name = "frame_log"]
+pub struct NewFrameLog {
+    pub iteration: i32,
+    pub frame: i32,
+    pub process_id: Option<i32>,
+    pub page: Option<i32>,
+}
+
+#[derive(Queryable, Serialize)]
+pub struct FrameLog {
+    pub id: i32,
+    pub iteration: i32,
+    pub frame: i32,
+    pub process_id: Option<i32>,
+    pub page: Option<i32>,
+}
+
+#[derive(Insertable)]
+#[table_name = "segment"]
+pub struct NewSegment<'a> {
+    pub process_id: i32,
+    pub number: i32,
+    pub name: &'a str,
+    pub size: i32,
+}
+
+#[derive(Queryable, Serialize)]
+pub struct Segment {
+    pub id: i32,
+    pub process_id: i32,
+    pub number: i32,
+    pub name: String,
+    pub size: i32,
+}
+
+#[derive(Queryable, Serialize, Insertable, Clone)]
+#[table_name = "segment_table"]
+pub struct SegmentTable {
+    pub process_id: i32,
+    pub segment: i32,
+    pub storage_partition_id: i32,
+    pub base: i32,
+    pub size: i32,
+}
+
+#[derive(Queryable, Serialize, Insertable, Clone)]
+#[table_name = "page_fault_log"]
+pub struct PageFaultLog {
+    pub id: i32,
+    pub iteration: i32,
+    pub process_id: i32,
+    pub page: i32,
+    pub frame: i32,
+    pub victim_page: Option<i32>,
+    pub faults: i32,
+}
+
+#[derive(Queryable, Serialize, Insertable, Clone)]
+#[table_name = "swap_log"]
+pub struct SwapLog {
+    pub id: i32,
+    pub iteration: i32,
+    pub process_id: i32,
+    pub storage_partition_id: i32,
+    pub state: i32,
+}
+
+#[derive(Queryable, Serialize, Insertable, Clone)]
+#[table_name = "fragmentation_log"]
+pub struct FragmentationLog {
+    pub iteration: i32,
+    pub free_memory: i32,
+    pub largest_hole: i32,
+    pub holes: i32,
+    pub external_fragmentation: f64,
+    pub utilization: f64,
+}
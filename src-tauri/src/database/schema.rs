@@ -30,6 +30,31 @@ table! {
         id -> Integer,
         process_id -> Integer,
         partition_number -> Integer,
+        admission_iteration -> Integer,
+        first_run_iteration -> Integer,
+        finish_iteration -> Integer,
+        waiting_time -> Integer,
+    }
+}
+
+table! {
+    fragmentation_log (iteration) {
+        iteration -> Integer,
+        free_memory -> Integer,
+        largest_hole -> Integer,
+        holes -> Integer,
+        external_fragmentation -> Double,
+        utilization -> Double,
+    }
+}
+
+table! {
+    frame_log (id) {
+        id -> Integer,
+        iteration -> Integer,
+        frame -> Integer,
+        process_id -> Nullable<Integer>,
+        page -> Nullable<Integer>,
     }
 }
 
@@ -39,6 +64,26 @@ table! {
     }
 }
 
+table! {
+    page_fault_log (id) {
+        id -> Integer,
+        iteration -> Integer,
+        process_id -> Integer,
+        page -> Integer,
+        frame -> Integer,
+        victim_page -> Nullable<Integer>,
+        faults -> Integer,
+    }
+}
+
+table! {
+    page_table (process_id, page) {
+        process_id -> Integer,
+        page -> Integer,
+        frame -> Integer,
+    }
+}
+
 table! {
     process (id) {
         id -> Integer,
@@ -46,6 +91,10 @@ table! {
         time -> Integer,
         size -> Integer,
         state -> Integer,
+        reference_string -> Text,
+        arrival -> Integer,
+        priority -> Integer,
+        io_bursts -> Text,
     }
 }
 
@@ -67,6 +116,35 @@ table! {
     }
 }
 
+table! {
+    process_state (process_id) {
+        process_id -> Integer,
+        time -> Integer,
+        state -> Integer,
+        cpu_time -> Integer,
+    }
+}
+
+table! {
+    segment (id) {
+        id -> Integer,
+        process_id -> Integer,
+        number -> Integer,
+        name -> Text,
+        size -> Integer,
+    }
+}
+
+table! {
+    segment_table (process_id, segment) {
+        process_id -> Integer,
+        segment -> Integer,
+        storage_partition_id -> Integer,
+        base -> Integer,
+        size -> Integer,
+    }
+}
+
 table! {
     storage_partition (id) {
         id -> Integer,
@@ -85,22 +163,45 @@ table! {
         storage_partition_id -> Integer,
         position -> Integer,
         size -> Integer,
+        internal_fragmentation -> Integer,
+    }
+}
+
+table! {
+    swap_log (id) {
+        id -> Integer,
+        iteration -> Integer,
+        process_id -> Integer,
+        storage_partition_id -> Integer,
+        state -> Integer,
     }
 }
 
 joinable!(finished_process -> process (process_id));
+joinable!(page_table -> process (process_id));
 joinable!(process_partition -> process (process_id));
 joinable!(process_partition -> storage_partition (storage_partition_id));
+joinable!(process_state -> process (process_id));
+joinable!(segment -> process (process_id));
+joinable!(segment_table -> process (process_id));
 
 allow_tables_to_appear_in_same_query!(
     compaction_log,
     condensation_log,
     configuration,
     finished_process,
+    fragmentation_log,
+    frame_log,
     iteration_log,
+    page_fault_log,
+    page_table,
     process,
     process_log,
     process_partition,
+    process_state,
+    segment,
+    segment_table,
     storage_partition,
     storage_partition_log,
+    swap_log,
 );
@@ -0,0 +1,97 @@
+use diesel::prelude::*;
+use diesel::sqlite::SqliteConnection;
+
+use super::{
+    configuration, delete_all_page_tables, delete_all_process_states,
+    delete_all_processes_partitions, delete_all_segment_tables, delete_all_storage_partitions,
+    establish_connection, schema,
+};
+
+use crate::error::SimError;
+use crate::model::simulator::{SimulationLog, Simulator};
+
+/// Saves everything the simulator has logged since the last save and its
+/// current memory in one transaction over one connection, so the database
+/// either has the whole iteration or is left as it was.
+pub fn save_simulation(simulator: &mut Simulator) -> Result<(), SimError> {
+    let connection = establish_connection()?;
+    connection.transaction(|| {
+        save_simulation_log(&connection, simulator.take_log())?;
+        save_simulation_state(&connection, simulator)
+    })
+}
+
+fn save_simulation_log(connection: &SqliteConnection, log: SimulationLog) -> Result<(), SimError> {
+    use schema::{
+        compaction_log, condensation_log, finished_process, fragmentation_log, frame_log,
+        iteration_log, page_fault_log, process_log, storage_partition_log, swap_log,
+    };
+
+    diesel::insert_into(iteration_log::table)
+        .values(&log.iterations)
+        .execute(connection)?;
+    diesel::insert_into(process_log::table)
+        .values(&log.process_logs)
+        .execute(connection)?;
+    diesel::insert_into(storage_partition_log::table)
+        .values(&log.storage_partition_logs)
+        .execute(connection)?;
+    diesel::insert_into(compaction_log::table)
+        .values(&log.compaction_logs)
+        .execute(connection)?;
+    diesel::insert_into(condensation_log::table)
+        .values(&log.condensation_logs)
+        .execute(connection)?;
+    diesel::insert_into(finished_process::table)
+        .values(&log.finished_processes)
+        .execute(connection)?;
+    diesel::insert_into(frame_log::table)
+        .values(&log.frame_logs)
+        .execute(connection)?;
+    diesel::insert_into(page_fault_log::table)
+        .values(&log.page_fault_logs)
+        .execute(connection)?;
+    diesel::insert_into(swap_log::table)
+        .values(&log.swap_logs)
+        .execute(connection)?;
+    diesel::insert_into(fragmentation_log::table)
+        .values(&log.fragmentation_logs)
+        .execute(connection)?;
+    Ok(())
+}
+
+fn save_simulation_state(
+    connection: &SqliteConnection,
+    simulator: &Simulator,
+) -> Result<(), SimError> {
+    use schema::{page_table, process_partition, process_state, segment_table, storage_partition};
+
+    // The partitions in the database are replaced by the ones in the
+    // simulator memory, and the states of the processes by the ones they have
+    // in the simulator. The processes keep the values they were created with,
+    // so the same workload can be run again.
+    delete_all_processes_partitions(connection)?;
+    delete_all_storage_partitions(connection)?;
+    delete_all_page_tables(connection)?;
+    delete_all_segment_tables(connection)?;
+    delete_all_process_states(connection)?;
+    diesel::insert_into(storage_partition::table)
+        .values(&simulator.get_storage_partitions())
+        .execute(connection)?;
+    diesel::insert_into(process_partition::table)
+        .values(&simulator.get_process_partitions())
+        .execute(connection)?;
+    diesel::insert_into(page_table::table)
+        .values(&simulator.get_page_tables())
+        .execute(connection)?;
+    diesel::insert_into(segment_table::table)
+        .values(&simulator.get_segment_tables())
+        .execute(connection)?;
+
+    diesel::insert_into(process_state::table)
+        .values(&simulator.get_process_states())
+        .execute(connection)?;
+
+    configuration::set_compactions(connection, simulator.compactions)?;
+    configuration::set_condensations(connection, simulator.condensations)
+}
@@ -0,0 +1,111 @@
+use diesel::result::{ConnectionError, DatabaseErrorKind};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use thiserror::Error;
+
+/// Everything that can go wrong in the simulator. The commands send it to the
+/// UI instead of crashing the app.
+#[derive(Debug, Error)]
+pub enum SimError {
+    #[error("{0} was not found")]
+    NotFound(String),
+    #[error("{0}")]
+    ConstraintViolation(String),
+    #[error("{0}")]
+    InvalidConfiguration(String),
+    /// The memory size is the most space a single process can take in the
+    /// memory, which depends on how it is allocated.
+    #[error(
+        "Process {process} has a size of {size} but the memory only has room for {memory_size}"
+    )]
+    MemoryTooSmall {
+        process: String,
+        size: i32,
+        memory_size: i32,
+    },
+    #[error("Could not open the database: {0}")]
+    Connection(#[from] ConnectionError),
+    #[error("{0}")]
+    Migration(String),
+    #[error("Database error: {0}")]
+    Database(#[source] diesel::result::Error),
+    /// A scenario or a report that could not be read or written.
+    #[error("{0}")]
+    File(String),
+    /// An event of the simulator that could not be sent to the UI.
+    #[error("Could not send the simulator event: {0}")]
+    Event(String),
+}
+
+impl SimError {
+    /// The name of the variant, so the UI can tell the errors apart.
+    pub fn get_kind(&self) -> &'static str {
+        match self {
+            SimError::NotFound(_) => "NotFound",
+            SimError::ConstraintViolation(_) => "ConstraintViolation",
+            SimError::InvalidConfiguration(_) => "InvalidConfiguration",
+            SimError::MemoryTooSmall { .. } => "MemoryTooSmall",
+            SimError::Connection(_) => "Connection",
+            SimError::Migration(_) => "Migration",
+            SimError::Database(_) => "Database",
+            SimError::File(_) => "File",
+            SimError::Event(_) => "Event",
+        }
+    }
+}
+
+/// The constraints of the tables are violated by the values, not by the
+/// database, so they are told apart from the other database errors.
+impl From<diesel::result::Error> for SimError {
+    fn from(error: diesel::result::Error) -> SimError {
+        match error {
+            diesel::result::Error::DatabaseError(
+                DatabaseErrorKind::UniqueViolation | DatabaseErrorKind::ForeignKeyViolation,
+                information,
+            ) => SimError::ConstraintViolation(information.message().to_string()),
+            error => SimError::Database(error),
+        }
+    }
+}
+
+/// The UI gets the kind of the error and the message to show.
+impl Serialize for SimError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("SimError", 2)?;
+        state.serialize_field("kind", self.get_kind())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_ui_gets_the_kind_and_the_message() {
+        let error = SimError::MemoryTooSmall {
+            process: "P1".to_string(),
+            size: 60,
+            memory_size: 50,
+        };
+        assert_eq!(
+            serde_json::to_value(&error).unwrap(),
+            serde_json::json!({
+                "kind": "MemoryTooSmall",
+                "message": "Process P1 has a size of 60 but the memory only has room for 50",
+            })
+        );
+    }
+
+    #[test]
+    fn the_violated_constraints_are_told_apart_from_the_database_errors() {
+        let error = SimError::from(diesel::result::Error::DatabaseError(
+            DatabaseErrorKind::UniqueViolation,
+            Box::new("UNIQUE constraint failed: process.name".to_string()),
+        ));
+        assert_eq!(error.get_kind(), "ConstraintViolation");
+        assert_eq!(error.to_string(), "UNIQUE constraint failed: process.name");
+        let error = SimError::from(diesel::result::Error::NotFound);
+        assert_eq!(error.get_kind(), "Database");
+    }
+}
@@ -0,0 +1,9 @@
+#[macro_use]
+extern crate diesel;
+#[macro_use]
+extern crate diesel_migrations;
+
+pub mod database;
+pub mod error;
+pub mod model;
+pub mod processor;
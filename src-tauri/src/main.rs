@@ -3,28 +3,104 @@
     windows_subsystem = "windows"
 )]
 
-#[macro_use]
-extern crate diesel;
+use std::env;
+use std::path::PathBuf;
 
-use crate::database::models;
-
-use self::database::configuration;
-
-pub mod database;
-mod model;
+use app::database::{self, configuration, location, models};
+use app::error::SimError;
+use app::model::allocation::AllocationEnum;
+use app::model::compaction::CompactionEnum;
+use app::model::comparison::{self, ComparisonResult};
+use app::model::event::SimulatorEvent;
+use app::model::generator::{generate_processes, GeneratorSettings};
+use app::model::placement::PlacementEnum;
+use app::model::process::{
+    create_io_bursts_from_value, create_reference_string_from_value, IoBurst, Process, Segment,
+};
+use app::model::replacement::ReplacementEnum;
+use app::model::report::{write_report, ProcessLogRecord, SimulationReport};
+use app::model::scenario::{create_scenario_process, read_scenario, write_scenario, Scenario};
+use app::model::scheduling::SchedulingEnum;
+use app::model::simulator::SimulatorSettings;
+use app::model::statistics::{get_simulation_statistics, SimulationStatistics};
+use app::processor;
 
 fn main() {
     tauri::Builder::default()
+        .setup(|app| {
+            // The app starts even if the database can't be used, so the UI
+            // can show why with check_database.
+            if let Err(error) =
+                location::init_database_path(get_database_argument(), app.path_resolver().app_dir())
+            {
+                eprintln!("{}", error);
+            }
+            Ok(())
+        })
+        .manage(processor::Processor::default())
         .invoke_handler(tauri::generate_handler![
+            check_database,
+            select_simulation_databases,
+            select_simulation_database,
+            create_simulation_database,
+            open_simulation_database,
             save_process,
             delete_all_processes,
             select_all_processes,
             select_process_with_id,
             delete_process_with_id,
             update_process_with_id,
+            import_scenario,
+            export_scenario,
+            export_results,
+            generate_workload,
             start_processor,
+            step_processor,
+            run_until,
+            pause_processor,
+            resume_processor,
+            reset_processor,
+            compare_policies,
             change_memory_size,
+            change_placement_strategy,
+            select_placement_strategy,
+            change_allocation_mode,
+            select_allocation_mode,
+            change_fixed_partition_sizes,
+            select_fixed_partition_sizes,
+            change_page_size,
+            select_page_size,
+            select_page_tables,
+            select_frame_logs,
+            select_process_segments,
+            select_segment_tables,
+            change_replacement_policy,
+            select_replacement_policy,
+            change_frames_per_process,
+            select_frames_per_process,
+            select_page_fault_logs,
+            change_swapping,
+            select_swapping,
+            change_swap_time,
+            select_swap_time,
+            select_swap_logs,
+            select_fragmentation_metrics,
+            change_scheduling_policy,
+            select_scheduling_policy,
+            change_quantum,
+            select_quantum,
+            change_cpus,
+            select_cpus,
+            change_aging,
+            select_aging,
+            change_io_devices,
+            select_io_devices,
+            change_compaction_policy,
+            select_compaction_policy,
+            change_compaction_threshold,
+            select_compaction_threshold,
             select_finished_processes,
+            select_process_statistics,
             select_compactions,
             select_condensations,
             select_compaction_logs,
@@ -34,173 +110,592 @@ fn main() {
         .expect("error while running tauri application");
 }
 
+/// The database can be chosen with --database <path> when the app starts.
+fn get_database_argument() -> Option<PathBuf> {
+    let args: Vec<String> = env::args().collect();
+    args.iter()
+        .position(|arg| arg == "--database")
+        .and_then(|i| args.get(i + 1))
+        .map(PathBuf::from)
+}
+
 #[tauri::command]
-fn save_process(name: String, time: i32, size: i32) -> bool {
-    if database::check_process_name_is_unique(None, &name) {
-        let process = model::process::Process::new(name, time, size);
-        database::create_process(process);
-        true
-    } else {
-        false
-    }
+fn check_database() -> Result<(), SimError> {
+    location::check_database()
+}
+
+#[tauri::command]
+fn select_simulation_databases() -> Vec<String> {
+    location::select_database_names()
+}
+
+#[tauri::command]
+fn select_simulation_database() -> String {
+    location::get_database_name()
+}
+
+/// Creates an empty simulation database and opens it, the current simulation
+/// stays in the database it was in.
+#[tauri::command]
+fn create_simulation_database(
+    name: String,
+    processor: tauri::State<processor::Processor>,
+) -> Result<(), SimError> {
+    processor.reset();
+    location::create_database(&name)
+}
+
+#[tauri::command]
+fn open_simulation_database(
+    name: String,
+    processor: tauri::State<processor::Processor>,
+) -> Result<(), SimError> {
+    processor.reset();
+    location::open_database(&name)
+}
+
+#[tauri::command]
+fn save_process(
+    name: String,
+    time: i32,
+    size: i32,
+    segments: Option<Vec<Segment>>,
+    reference_string: Option<Vec<i32>>,
+    arrival: Option<i32>,
+    priority: Option<i32>,
+    io_bursts: Option<Vec<IoBurst>>,
+) -> Result<(), SimError> {
+    database::check_process_name_is_unique(None, &name)?;
+    let mut process = Process::new(name, time, size);
+    process.set_segments(segments.unwrap_or(Vec::new()))?;
+    process.set_reference_string(
+        reference_string.unwrap_or(Vec::new()),
+        configuration::get_page_size()?,
+    )?;
+    process.set_arrival(arrival.unwrap_or(1))?;
+    process.set_priority(priority.unwrap_or(0))?;
+    process.set_io_bursts(io_bursts.unwrap_or(Vec::new()))?;
+    database::create_process(process)
+}
+
+#[tauri::command]
+fn start_processor(
+    window: tauri::Window,
+    processor: tauri::State<processor::Processor>,
+) -> Result<bool, SimError> {
+    processor.start(emit_simulator_event(&window))
+}
+
+#[tauri::command]
+fn step_processor(
+    window: tauri::Window,
+    processor: tauri::State<processor::Processor>,
+) -> Result<bool, SimError> {
+    processor.step(emit_simulator_event(&window))
+}
+
+// The running commands are async, so they run outside of the main thread and
+// the processor can be paused while they are running.
+#[tauri::command]
+async fn run_until(
+    window: tauri::Window,
+    processor: tauri::State<'_, processor::Processor>,
+    iteration: i32,
+) -> Result<bool, SimError> {
+    processor.run_until(iteration, emit_simulator_event(&window))
+}
+
+#[tauri::command]
+fn pause_processor(processor: tauri::State<processor::Processor>) {
+    processor.pause();
 }
 
 #[tauri::command]
-fn start_processor() -> bool {
-    use model::process::create_process_from_model;
+async fn resume_processor(
+    window: tauri::Window,
+    processor: tauri::State<'_, processor::Processor>,
+) -> Result<bool, SimError> {
+    processor.resume(emit_simulator_event(&window))
+}
 
-    // Start database base configuration
-    database::init_configuration();
+#[tauri::command]
+fn reset_processor(processor: tauri::State<processor::Processor>) {
+    processor.reset();
+}
 
-    database::clear_database();
-    database::create_iteration_log();
+/// Runs the workload from the start with every configuration, without
+/// touching the simulation in the database.
+#[tauri::command]
+fn compare_policies(
+    configurations: Vec<SimulatorSettings>,
+) -> Result<Vec<ComparisonResult>, SimError> {
+    let processes = database::select_workload()?;
+    comparison::compare_policies(&processes, &configurations)
+}
 
-    // This means there is no ready processes in the processor, so it has finished
-    println!("Adding processes to memory...");
-    if !database::add_processes_to_memory() {
-        println!("Empty processes at start.");
-        return true;
+#[tauri::command]
+fn update_process_with_id(
+    id: i32,
+    name: String,
+    time: i32,
+    size: i32,
+    segments: Option<Vec<Segment>>,
+    reference_string: Option<Vec<i32>>,
+    arrival: Option<i32>,
+    priority: Option<i32>,
+    io_bursts: Option<Vec<IoBurst>>,
+) -> Result<(), SimError> {
+    database::check_process_name_is_unique(Some(id), &name)?;
+    let mut process = Process::new(name, time, size);
+    // The reference string, the arrival, the priority and the I/O bursts are
+    // kept if new ones are not sent.
+    let saved_process = database::select_process_with_id(id)?;
+    // The segments are only changed if they are sent, they go first as they
+    // change the size the pages of the reference string are checked with.
+    let has_new_segments = segments.is_some();
+    if let Some(segments) = segments {
+        process.set_segments(segments)?;
     }
-    println!("Finished adding processes to memory.");
-    // Here I generate a new partition with the remaining empty space, this
-    // should only be run once, and it is when the processor starts.
-    database::create_storage_partition_from_remaining_space();
-    // Log the start of the partitions
-    database::create_storage_partition_logs();
-    loop {
-        database::create_iteration_log();
-        database::select_all_processes_from_processes_partitions()
+    process.set_reference_string(
+        match reference_string {
+            Some(reference_string) => reference_string,
+            None => create_reference_string_from_value(&saved_process.reference_string)?,
+        },
+        configuration::get_page_size()?,
+    )?;
+    process.set_arrival(arrival.unwrap_or(saved_process.arrival))?;
+    process.set_priority(priority.unwrap_or(saved_process.priority))?;
+    process.set_io_bursts(match io_bursts {
+        Some(io_bursts) => io_bursts,
+        None => create_io_bursts_from_value(&saved_process.io_bursts)?,
+    })?;
+    database::update_process_with_id(id, &process, has_new_segments)
+}
+
+/// Replaces the processes and the settings with the ones in the scenario file.
+#[tauri::command]
+fn import_scenario(path: String) -> Result<(), SimError> {
+    let scenario = read_scenario(&path)?;
+    let processes = scenario.create_processes()?;
+    database::replace_workload(processes, Some(&scenario.settings))
+}
+
+/// Replaces the processes with random ones that fit the memory size.
+#[tauri::command]
+fn generate_workload(generator: GeneratorSettings) -> Result<(), SimError> {
+    let processes = generate_processes(&generator, configuration::get_memory_size()?)?;
+    database::replace_workload(processes, None)
+}
+
+/// Saves the workload as it was created and the settings to a scenario file,
+/// so it can be imported again after it has been run.
+#[tauri::command]
+fn export_scenario(path: String) -> Result<(), SimError> {
+    let processes = database::select_workload()?;
+    let scenario = Scenario {
+        settings: configuration::get_simulator_settings()?,
+        generator: None,
+        processes: processes.iter().map(create_scenario_process).collect(),
+    };
+    write_scenario(&path, &scenario)
+}
+
+/// Saves the logs of the last simulation and the statistics of its finished
+/// processes, as one JSON report or as CSV files in a directory.
+#[tauri::command]
+fn export_results(path: String) -> Result<(), SimError> {
+    let process_logs = database::select_all_process_logs_with_names()?;
+    let storage_partition_logs = database::select_all_storage_partition_logs()?;
+    let report = SimulationReport {
+        settings: configuration::get_simulator_settings()?,
+        process_logs: process_logs
             .iter()
-            .map(|process| create_process_from_model(process))
-            .for_each(|mut process| {
-                process.process();
-                database::update_process_with_id(process.id.unwrap(), &process)
-                    .expect("Could not update process");
-                database::create_process_log(process.id.unwrap());
-            });
-        database::create_storage_partition_logs();
-
-        // Before we try to add a new process to the processor and its partitions,
-        // we need to check if the partitions that the processor has can be
-        // compacted, so start by swapping process partitions with empty partitions.
-        println!("Swapping partitions...");
-        database::swap_process_partitions_with_empty_partitions();
-        println!("finished swapping partitions...");
-        // After the swap, merge all the empty swapped partitions.
-        println!("Merging partitions...");
-        database::merge_storage_partitions();
-        println!("finished Merging partitions...");
-        // Try and add the remaining processes to the memory with the new big
-        // compacted partition if it did that.
-        //
-        // If the processor could'nt add processes after the compactation it
-        // means one of two things:
-        // 1. The processor has no ready processes but it hasn't finished.
-        // 2. The processor has no ready processes and it has finished.
-        if !database::add_processes_to_memory() {
-            // The processor does not have ready processes and the partitions are
-            // empty, so the processor has finished.
-            if database::select_all_processes_from_processes_partitions().len() == 0 {
-                return true;
-            }
+            .map(|(process_log, name)| ProcessLogRecord::new(process_log, name))
+            .collect::<Result<_, _>>()?,
+        storage_partition_logs: storage_partition_logs
+            .iter()
+            .map(|partition_log| models::NewStoragePartitionLog {
+                iteration: partition_log.iteration,
+                storage_partition_id: partition_log.storage_partition_id,
+                position: partition_log.position,
+                size: partition_log.size,
+                internal_fragmentation: partition_log.internal_fragmentation,
+            })
+            .collect(),
+        compaction_logs: database::select_all_compaction_logs()?,
+        condensation_logs: database::select_all_condensation_logs()?,
+        statistics: get_simulation_statistics(&database::select_all_finished_processes()?),
+        compactions: configuration::get_compactions()?,
+        condensations: configuration::get_condensations()?,
+    };
+    write_report(&path, &report)
+}
+
+#[tauri::command]
+fn delete_all_processes() -> Result<(), SimError> {
+    database::delete_all_processes()
+}
+
+#[tauri::command]
+fn select_process_with_id(id: i32) -> Result<models::Process, SimError> {
+    database::select_process_with_id(id)
+}
+
+/// The processes with the time left and the state they have in the current
+/// simulation, the ones that are not in it are shown as they were created.
+#[tauri::command]
+fn select_all_processes() -> Result<Vec<models::Process>, SimError> {
+    let process_states = database::select_all_process_states()?;
+    let mut processes = database::select_all_processes()?;
+    for process in processes.iter_mut() {
+        if let Some(process_state) = process_states
+            .iter()
+            .find(|process_state| process_state.process_id == process.id)
+        {
+            process.time = process_state.time;
+            process.state = process_state.state;
         }
     }
+    Ok(processes)
+}
+
+#[tauri::command]
+fn select_all_storage_partitions() -> Result<Vec<models::StoragePartition>, SimError> {
+    database::select_all_storage_partitions()
+}
+
+#[tauri::command]
+fn select_all_storage_partition_logs() -> Result<Vec<models::StoragePartitionLog>, SimError> {
+    database::select_all_storage_partition_logs()
+}
+
+#[tauri::command]
+fn select_all_process_logs() -> Result<Vec<(String, i32, i32, i32)>, SimError> {
+    database::select_all_process_logs()
+}
+
+#[tauri::command]
+fn delete_process_with_id(id: i32) -> Result<(), SimError> {
+    database::delete_process_with_id(id)
+}
+
+#[tauri::command]
+fn change_memory_size(size: i32) -> Result<(), SimError> {
+    if size <= 0 {
+        return Err(SimError::InvalidConfiguration(
+            "The memory size has to be positive".to_string(),
+        ));
+    }
+    // The fixed partitions that have been set still have to fit in the
+    // memory.
+    let fixed_partitions_size: i32 = configuration::get_fixed_partition_sizes()?.iter().sum();
+    if fixed_partitions_size > size {
+        return Err(SimError::InvalidConfiguration(format!(
+            "The fixed partitions take {} so the memory can't be smaller",
+            fixed_partitions_size
+        )));
+    }
+    database::configuration::set_memory_size(size)
+}
+
+#[tauri::command]
+fn change_placement_strategy(strategy: i32) -> Result<(), SimError> {
+    if strategy < 0 || strategy > PlacementEnum::NextFit as i32 {
+        return Err(SimError::InvalidConfiguration(
+            "The placement strategy is not recognized".to_string(),
+        ));
+    }
+    database::configuration::set_placement_strategy(strategy)
+}
+
+#[tauri::command]
+fn select_placement_strategy() -> Result<i32, SimError> {
+    configuration::get_placement_strategy()
+}
+
+#[tauri::command]
+fn change_allocation_mode(mode: i32) -> Result<(), SimError> {
+    if mode < 0 || mode > AllocationEnum::VirtualMemory as i32 {
+        return Err(SimError::InvalidConfiguration(
+            "The allocation mode is not recognized".to_string(),
+        ));
+    }
+    database::configuration::set_allocation_mode(mode)
+}
+
+#[tauri::command]
+fn select_allocation_mode() -> Result<i32, SimError> {
+    configuration::get_allocation_mode()
+}
+
+#[tauri::command]
+fn change_fixed_partition_sizes(sizes: Vec<i32>) -> Result<(), SimError> {
+    // Every partition needs some space, and all of them have to fit in the
+    // memory.
+    if sizes.iter().any(|&size| size <= 0)
+        || sizes.iter().sum::<i32>() > configuration::get_memory_size()?
+    {
+        return Err(SimError::InvalidConfiguration(
+            "The fixed partitions have to be positive and fit in memory".to_string(),
+        ));
+    }
+    database::configuration::set_fixed_partition_sizes(&sizes)
+}
+
+#[tauri::command]
+fn select_fixed_partition_sizes() -> Result<Vec<i32>, SimError> {
+    configuration::get_fixed_partition_sizes()
+}
+
+#[tauri::command]
+fn change_page_size(size: i32) -> Result<(), SimError> {
+    if size <= 0 {
+        return Err(SimError::InvalidConfiguration(
+            "The page size has to be positive".to_string(),
+        ));
+    }
+    database::configuration::set_page_size(size)
+}
+
+#[tauri::command]
+fn select_page_size() -> Result<i32, SimError> {
+    configuration::get_page_size()
+}
+
+#[tauri::command]
+fn change_replacement_policy(policy: i32) -> Result<(), SimError> {
+    if policy < 0 || policy > ReplacementEnum::Optimal as i32 {
+        return Err(SimError::InvalidConfiguration(
+            "The replacement policy is not recognized".to_string(),
+        ));
+    }
+    database::configuration::set_replacement_policy(policy)
+}
+
+#[tauri::command]
+fn select_replacement_policy() -> Result<i32, SimError> {
+    configuration::get_replacement_policy()
+}
+
+#[tauri::command]
+fn change_frames_per_process(frames: i32) -> Result<(), SimError> {
+    if frames <= 0 {
+        return Err(SimError::InvalidConfiguration(
+            "The frames per process have to be positive".to_string(),
+        ));
+    }
+    database::configuration::set_frames_per_process(frames)
+}
+
+#[tauri::command]
+fn select_frames_per_process() -> Result<i32, SimError> {
+    configuration::get_frames_per_process()
+}
+
+#[tauri::command]
+fn change_swapping(swapping: bool) -> Result<(), SimError> {
+    database::configuration::set_swapping(swapping)
+}
+
+#[tauri::command]
+fn select_swapping() -> Result<bool, SimError> {
+    configuration::get_swapping()
+}
+
+#[tauri::command]
+fn change_swap_time(time: i32) -> Result<(), SimError> {
+    if time < 1 {
+        return Err(SimError::InvalidConfiguration(
+            "The swap time has to be at least 1".to_string(),
+        ));
+    }
+    database::configuration::set_swap_time(time)
 }
 
 #[tauri::command]
-fn update_process_with_id(id: i32, name: String, time: i32, size: i32) -> bool {
-    if database::check_process_name_is_unique(Some(id), &name) {
-        let process = model::process::Process::new(name, time, size);
-        database::update_process_with_id(id, &process).is_ok()
-    } else {
-        false
+fn select_swap_time() -> Result<i32, SimError> {
+    configuration::get_swap_time()
+}
+
+#[tauri::command]
+fn change_scheduling_policy(policy: i32) -> Result<(), SimError> {
+    if policy < 0 || policy > SchedulingEnum::Priority as i32 {
+        return Err(SimError::InvalidConfiguration(
+            "The scheduling policy is not recognized".to_string(),
+        ));
     }
+    database::configuration::set_scheduling_policy(policy)
 }
 
 #[tauri::command]
-fn delete_all_processes() -> bool {
-    database::delete_all_processes().is_ok()
+fn select_scheduling_policy() -> Result<i32, SimError> {
+    configuration::get_scheduling_policy()
 }
 
 #[tauri::command]
-fn select_process_with_id(id: i32) -> Result<models::Process, bool> {
-    let process = database::select_process_with_id(id);
-    if process.is_ok() {
-        Ok(process.unwrap())
-    } else {
-        Err(false)
+fn change_quantum(quantum: i32) -> Result<(), SimError> {
+    if quantum <= 0 {
+        return Err(SimError::InvalidConfiguration(
+            "The quantum has to be positive".to_string(),
+        ));
     }
+    database::configuration::set_quantum(quantum)
 }
 
 #[tauri::command]
-fn select_all_processes() -> Result<Vec<models::Process>, bool> {
-    let processes = database::select_all_processes();
+fn select_quantum() -> Result<i32, SimError> {
+    configuration::get_quantum()
+}
 
-    if processes.is_ok() {
-        Ok(processes.unwrap())
-    } else {
-        Err(false)
+#[tauri::command]
+fn change_cpus(cpus: i32) -> Result<(), SimError> {
+    if cpus < 0 {
+        return Err(SimError::InvalidConfiguration(
+            "The number of CPUs can't be negative".to_string(),
+        ));
     }
+    database::configuration::set_cpus(cpus)
 }
 
 #[tauri::command]
-fn select_all_storage_partitions() -> Vec<models::StoragePartition> {
-    return database::select_all_storage_partitions();
+fn select_cpus() -> Result<i32, SimError> {
+    configuration::get_cpus()
 }
 
 #[tauri::command]
-fn select_all_storage_partition_logs() -> Result<Vec<models::StoragePartitionLog>, bool> {
-    let partitions = database::select_all_storage_partition_logs();
+fn change_aging(aging: i32) -> Result<(), SimError> {
+    if aging < 0 {
+        return Err(SimError::InvalidConfiguration(
+            "The aging can't be negative".to_string(),
+        ));
+    }
+    database::configuration::set_aging(aging)
+}
 
-    if partitions.is_ok() {
-        Ok(partitions.unwrap())
-    } else {
-        Err(false)
+#[tauri::command]
+fn select_aging() -> Result<i32, SimError> {
+    configuration::get_aging()
+}
+
+#[tauri::command]
+fn change_io_devices(devices: i32) -> Result<(), SimError> {
+    if devices <= 0 {
+        return Err(SimError::InvalidConfiguration(
+            "The number of I/O devices has to be positive".to_string(),
+        ));
     }
+    database::configuration::set_io_devices(devices)
 }
 
 #[tauri::command]
-fn select_all_process_logs() -> Result<Vec<(String, i32, i32, i32)>, bool> {
-    let process_logs = database::select_all_process_logs();
+fn select_io_devices() -> Result<i32, SimError> {
+    configuration::get_io_devices()
+}
 
-    if process_logs.is_ok() {
-        Ok(process_logs.unwrap())
-    } else {
-        Err(false)
+#[tauri::command]
+fn change_compaction_policy(policy: i32) -> Result<(), SimError> {
+    if policy < 0 || policy > CompactionEnum::Never as i32 {
+        return Err(SimError::InvalidConfiguration(
+            "The compaction policy is not recognized".to_string(),
+        ));
     }
+    database::configuration::set_compaction_policy(policy)
+}
+
+#[tauri::command]
+fn select_compaction_policy() -> Result<i32, SimError> {
+    configuration::get_compaction_policy()
 }
 
 #[tauri::command]
-fn delete_process_with_id(id: i32) -> bool {
-    database::delete_process_with_id(id).is_ok()
+fn change_compaction_threshold(threshold: i32) -> Result<(), SimError> {
+    if !(0..=100).contains(&threshold) {
+        return Err(SimError::InvalidConfiguration(
+            "The compaction threshold has to be a percentage".to_string(),
+        ));
+    }
+    database::configuration::set_compaction_threshold(threshold)
 }
 
 #[tauri::command]
-fn change_memory_size(size: i32) {
-    database::configuration::set_memory_size(size);
+fn select_compaction_threshold() -> Result<i32, SimError> {
+    configuration::get_compaction_threshold()
 }
 
 #[tauri::command]
-fn select_finished_processes() -> Vec<(models::FinishedProcess, models::Process)> {
+fn select_finished_processes() -> Result<Vec<(models::FinishedProcess, models::Process)>, SimError>
+{
     database::select_all_finished_processes()
 }
 
 #[tauri::command]
-fn select_compactions() -> i32 {
+fn select_process_statistics() -> Result<SimulationStatistics, SimError> {
+    Ok(get_simulation_statistics(
+        &database::select_all_finished_processes()?,
+    ))
+}
+
+#[tauri::command]
+fn select_compactions() -> Result<i32, SimError> {
     configuration::get_compactions()
 }
 
 #[tauri::command]
-fn select_condensations() -> i32 {
+fn select_condensations() -> Result<i32, SimError> {
     configuration::get_condensations()
 }
 
 #[tauri::command]
-fn select_compaction_logs() -> Vec<models::CompactionLog> {
+fn select_compaction_logs() -> Result<Vec<models::CompactionLog>, SimError> {
     database::select_all_compaction_logs()
 }
 
 #[tauri::command]
-fn select_condensation_logs() -> Vec<models::CondensationLog> {
+fn select_condensation_logs() -> Result<Vec<models::CondensationLog>, SimError> {
     database::select_all_condensation_logs()
 }
+
+#[tauri::command]
+fn select_page_tables() -> Result<Vec<models::PageTable>, SimError> {
+    database::select_all_page_tables()
+}
+
+#[tauri::command]
+fn select_frame_logs() -> Result<Vec<models::FrameLog>, SimError> {
+    database::select_all_frame_logs()
+}
+
+#[tauri::command]
+fn select_process_segments(id: i32) -> Result<Vec<models::Segment>, SimError> {
+    database::select_segments_with_process_id(id)
+}
+
+#[tauri::command]
+fn select_segment_tables() -> Result<Vec<models::SegmentTable>, SimError> {
+    database::select_all_segment_tables()
+}
+
+#[tauri::command]
+fn select_page_fault_logs() -> Result<Vec<models::PageFaultLog>, SimError> {
+    database::select_all_page_fault_logs()
+}
+
+#[tauri::command]
+fn select_swap_logs() -> Result<Vec<models::SwapLog>, SimError> {
+    database::select_all_swap_logs()
+}
+
+#[tauri::command]
+fn select_fragmentation_metrics() -> Result<Vec<models::FragmentationLog>, SimError> {
+    database::select_all_fragmentation_logs()
+}
+
+fn emit_simulator_event(
+    window: &tauri::Window,
+) -> impl Fn(SimulatorEvent) -> Result<(), SimError> + '_ {
+    move |event| {
+        window
+            .emit(event.get_event_name(), event)
+            .map_err(|error| SimError::Event(error.to_string()))
+    }
+}
@@ -0,0 +1,23 @@
+#[derive(Copy, Clone, PartialEq)]
+pub enum AllocationEnum {
+    DynamicPartitions,
+    BuddySystem,
+    FixedPartitions,
+    Paging,
+    Segmentation,
+    VirtualMemory,
+}
+
+pub fn get_allocation_from_enum(value: i32) -> AllocationEnum {
+    match value {
+        value if value == AllocationEnum::DynamicPartitions as i32 => {
+            AllocationEnum::DynamicPartitions
+        }
+        value if value == AllocationEnum::BuddySystem as i32 => AllocationEnum::BuddySystem,
+        value if value == AllocationEnum::FixedPartitions as i32 => AllocationEnum::FixedPartitions,
+        value if value == AllocationEnum::Paging as i32 => AllocationEnum::Paging,
+        value if value == AllocationEnum::Segmentation as i32 => AllocationEnum::Segmentation,
+        value if value == AllocationEnum::VirtualMemory as i32 => AllocationEnum::VirtualMemory,
+        _ => panic!("Allocation mode not recognized"),
+    }
+}
@@ -0,0 +1,25 @@
+/// When the partitions of the processes are moved to one end of the memory,
+/// so the empty partitions between them can be merged into one. The empty
+/// partitions that are next to each other are merged with every policy.
+#[derive(Copy, Clone, PartialEq)]
+pub enum CompactionEnum {
+    /// Before the processes enter in every iteration.
+    Always,
+    /// Only when a process doesn't fit in any empty partition, but it would
+    /// fit in all the free memory together.
+    OnFailure,
+    /// Before the processes enter, when the external fragmentation has
+    /// reached the threshold.
+    Threshold,
+    Never,
+}
+
+pub fn get_compaction_from_enum(value: i32) -> CompactionEnum {
+    match value {
+        value if value == CompactionEnum::Always as i32 => CompactionEnum::Always,
+        value if value == CompactionEnum::OnFailure as i32 => CompactionEnum::OnFailure,
+        value if value == CompactionEnum::Threshold as i32 => CompactionEnum::Threshold,
+        value if value == CompactionEnum::Never as i32 => CompactionEnum::Never,
+        _ => panic!("Compaction policy not recognized"),
+    }
+}
@@ -0,0 +1,160 @@
+use serde::Serialize;
+
+use crate::error::SimError;
+
+use super::process::Process;
+use super::report::create_report_from_simulator;
+use super::simulator::{Simulator, SimulatorSettings};
+
+/// How a workload ran with one of the configurations being compared.
+#[derive(Serialize)]
+pub struct ComparisonResult {
+    pub settings: SimulatorSettings,
+    /// The iterations until the processor finished.
+    pub iterations: i32,
+    pub compactions: i32,
+    pub condensations: i32,
+    /// The processes that finished. Every process has to fit in the memory
+    /// of every configuration, the comparison is an error otherwise.
+    pub finished_processes: i32,
+    /// The average of the external fragmentation of every iteration.
+    pub average_fragmentation: f64,
+    /// The average of the finished processes.
+    pub average_turnaround_time: f64,
+}
+
+/// Runs the same processes with every configuration, each in its own
+/// simulator, and returns the results in the same order as the
+/// configurations. The error says which configuration is not valid, or which
+/// process doesn't fit in the memory of one of them.
+pub fn compare_policies(
+    processes: &[Process],
+    configurations: &[SimulatorSettings],
+) -> Result<Vec<ComparisonResult>, SimError> {
+    for (i, settings) in configurations.iter().enumerate() {
+        settings.validate().map_err(|error| {
+            SimError::InvalidConfiguration(format!("Configuration {}: {}", i + 1, error))
+        })?;
+        settings.check_processes(processes)?;
+    }
+
+    configurations
+        .iter()
+        .map(|settings| {
+            let mut simulator = Simulator::new(settings, processes.to_vec());
+            simulator.run();
+            let log = simulator.take_log();
+            let report = create_report_from_simulator(settings, &simulator, &log)?;
+            let average_fragmentation = if log.fragmentation_logs.is_empty() {
+                0.0
+            } else {
+                log.fragmentation_logs
+                    .iter()
+                    .map(|fragmentation_log| fragmentation_log.external_fragmentation)
+                    .sum::<f64>()
+                    / log.fragmentation_logs.len() as f64
+            };
+
+            Ok(ComparisonResult {
+                settings: settings.clone(),
+                iterations: simulator.iteration,
+                compactions: simulator.compactions,
+                condensations: simulator.condensations,
+                finished_processes: report.statistics.processes.len() as i32,
+                average_fragmentation,
+                average_turnaround_time: report.statistics.average_turnaround_time,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::compaction::CompactionEnum;
+    use crate::model::placement::PlacementEnum;
+
+    /// When A and C finish they leave holes of 10 and 5, E takes the first
+    /// hole with first fit, so F only fits after compacting or after B or D
+    /// finish.
+    fn create_workload() -> Vec<Process> {
+        let mut processes = vec![
+            Process::new("A".to_string(), 1, 10),
+            Process::new("B".to_string(), 10, 10),
+            Process::new("C".to_string(), 1, 5),
+            Process::new("D".to_string(), 10, 20),
+            Process::new("E".to_string(), 3, 4),
+            Process::new("F".to_string(), 3, 10),
+        ];
+        processes[4].set_arrival(5).unwrap();
+        processes[5].set_arrival(5).unwrap();
+        processes
+    }
+
+    fn create_settings(
+        placement_strategy: PlacementEnum,
+        compaction_policy: CompactionEnum,
+    ) -> SimulatorSettings {
+        SimulatorSettings {
+            placement_strategy: placement_strategy as i32,
+            compaction_policy: compaction_policy as i32,
+            ..SimulatorSettings::default()
+        }
+    }
+
+    #[test]
+    fn the_placement_strategies_are_compared_on_the_same_workload() {
+        let results = compare_policies(
+            &create_workload(),
+            &[
+                create_settings(PlacementEnum::FirstFit, CompactionEnum::Never),
+                create_settings(PlacementEnum::BestFit, CompactionEnum::Never),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(
+            results[0].settings.placement_strategy,
+            PlacementEnum::FirstFit as i32
+        );
+        assert_eq!(
+            results[1].settings.placement_strategy,
+            PlacementEnum::BestFit as i32
+        );
+        assert!(results.iter().all(|result| result.finished_processes == 6));
+        assert!(results[0].average_turnaround_time > results[1].average_turnaround_time);
+        assert!(results[0].average_fragmentation > results[1].average_fragmentation);
+    }
+
+    #[test]
+    fn the_memory_is_compacted_with_the_compaction_policy() {
+        let results = compare_policies(
+            &create_workload(),
+            &[
+                create_settings(PlacementEnum::FirstFit, CompactionEnum::Always),
+                create_settings(PlacementEnum::FirstFit, CompactionEnum::OnFailure),
+                create_settings(PlacementEnum::BestFit, CompactionEnum::OnFailure),
+                create_settings(PlacementEnum::FirstFit, CompactionEnum::Never),
+            ],
+        )
+        .unwrap();
+
+        let compactions: Vec<i32> = results.iter().map(|result| result.compactions).collect();
+        assert_eq!(compactions, vec![1, 1, 0, 0]);
+        // Without compacting, F waits for B or D to finish.
+        assert!(results[3].average_turnaround_time > results[1].average_turnaround_time);
+    }
+
+    #[test]
+    fn a_process_that_does_not_fit_is_an_error() {
+        let settings = SimulatorSettings {
+            memory_size: 15,
+            ..SimulatorSettings::default()
+        };
+        assert!(matches!(
+            compare_policies(&create_workload(), &[settings]),
+            Err(SimError::MemoryTooSmall { .. })
+        ));
+    }
+}
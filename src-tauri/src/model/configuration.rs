@@ -1,7 +1,28 @@
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug)]
 pub enum SettingName {
     MemorySize,
+    /// Every simulation starts from the first iteration, so the partition
+    /// numbers and the next fit pointer only live in the simulator. The
+    /// settings are not saved anymore, they are kept so the ids of the
+    /// settings after them stay the same.
     PartitionConsecutiveNumber,
     Compactions,
     Condensations,
+    PlacementStrategy,
+    /// Not saved anymore, like the partition consecutive number.
+    NextFitPosition,
+    AllocationMode,
+    FixedPartitionSizes,
+    PageSize,
+    ReplacementPolicy,
+    FramesPerProcess,
+    Swapping,
+    SwapTime,
+    SchedulingPolicy,
+    Quantum,
+    Cpus,
+    Aging,
+    IoDevices,
+    CompactionPolicy,
+    CompactionThreshold,
 }
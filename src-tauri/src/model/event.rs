@@ -0,0 +1,54 @@
+use serde::Serialize;
+
+use crate::database::models;
+
+/// Something that happened while the simulator was running, it is sent to the
+/// frontend as it happens so the memory map can be animated.
+#[derive(Clone, Serialize)]
+#[serde(untagged)]
+pub enum SimulatorEvent {
+    ProcessAdmitted(models::ProcessPartition),
+    ProcessFinished(models::ProcessLog),
+    PartitionSplit(PartitionSplit),
+    CompactionSwap(models::CompactionLog),
+    Condensation(models::CondensationLog),
+    PageFault(models::PageFaultLog),
+    Swap(models::SwapLog),
+    IterationComplete(IterationComplete),
+}
+
+#[derive(Clone, Serialize)]
+pub struct PartitionSplit {
+    pub iteration: i32,
+    pub partition: i32,
+    pub partition_size: i32,
+    pub process_partition: i32,
+    pub process_partition_size: i32,
+    pub remaining_partition: i32,
+    pub remaining_partition_size: i32,
+}
+
+#[derive(Clone, Serialize)]
+pub struct IterationComplete {
+    pub iteration: i32,
+    pub has_finished: bool,
+    pub storage_partitions: Vec<models::StoragePartition>,
+    pub process_partitions: Vec<models::ProcessPartition>,
+    pub page_tables: Vec<models::PageTable>,
+    pub segment_tables: Vec<models::SegmentTable>,
+}
+
+impl SimulatorEvent {
+    pub fn get_event_name(&self) -> &'static str {
+        match self {
+            SimulatorEvent::ProcessAdmitted(_) => "process-admitted",
+            SimulatorEvent::ProcessFinished(_) => "process-finished",
+            SimulatorEvent::PartitionSplit(_) => "partition-split",
+            SimulatorEvent::CompactionSwap(_) => "compaction-swap",
+            SimulatorEvent::Condensation(_) => "condensation",
+            SimulatorEvent::PageFault(_) => "page-fault",
+            SimulatorEvent::Swap(_) => "swap",
+            SimulatorEvent::IterationComplete(_) => "iteration-complete",
+        }
+    }
+}
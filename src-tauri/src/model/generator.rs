@@ -0,0 +1,198 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use rand_distr::{Distribution, Exp, Normal, Uniform};
+use serde::{Deserialize, Serialize};
+
+use crate::error::SimError;
+
+use super::process::Process;
+
+#[derive(Copy, Clone)]
+pub enum DistributionEnum {
+    Uniform,
+    Normal,
+    Exponential,
+}
+
+/// How a random value is drawn, saved as the number of its enum.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct DistributionSettings {
+    pub distribution: i32,
+    pub mean: f64,
+    /// How far the values go from the mean with the uniform distribution, or
+    /// the standard deviation with the normal one. The exponential
+    /// distribution only needs its mean.
+    pub deviation: f64,
+}
+
+/// The processes of a random workload. The same seed always generates the
+/// same processes.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GeneratorSettings {
+    pub processes: i32,
+    pub seed: u64,
+    /// The sizes are fractions of the memory size, so a workload fits any
+    /// memory.
+    pub size: DistributionSettings,
+    /// The times are in iterations.
+    pub time: DistributionSettings,
+}
+
+impl Default for GeneratorSettings {
+    fn default() -> GeneratorSettings {
+        GeneratorSettings {
+            processes: 10,
+            seed: 0,
+            size: DistributionSettings {
+                distribution: DistributionEnum::Uniform as i32,
+                mean: 0.2,
+                deviation: 0.1,
+            },
+            time: DistributionSettings {
+                distribution: DistributionEnum::Uniform as i32,
+                mean: 10.0,
+                deviation: 5.0,
+            },
+        }
+    }
+}
+
+impl DistributionSettings {
+    /// The error says what is not valid, the name is the one of the value.
+    fn validate(&self, name: &str) -> Result<(), SimError> {
+        if self.distribution < 0 || self.distribution > DistributionEnum::Exponential as i32 {
+            return Err(SimError::InvalidConfiguration(format!(
+                "The distribution of the {} is not recognized",
+                name
+            )));
+        }
+        if !self.mean.is_finite() || self.mean <= 0.0 {
+            return Err(SimError::InvalidConfiguration(format!(
+                "The mean of the {} has to be positive",
+                name
+            )));
+        }
+        if !self.deviation.is_finite() || self.deviation < 0.0 {
+            return Err(SimError::InvalidConfiguration(format!(
+                "The deviation of the {} can't be negative",
+                name
+            )));
+        }
+        Ok(())
+    }
+
+    /// Draws a value, with the mean and the deviation multiplied by the scale.
+    /// The error says why the distribution can't be used with them.
+    fn sample(&self, rng: &mut ChaCha8Rng, scale: f64, name: &str) -> Result<i32, SimError> {
+        let mean = self.mean * scale;
+        let deviation = self.deviation * scale;
+        let invalid_distribution = |error: String| {
+            SimError::InvalidConfiguration(format!(
+                "The distribution of the {} can't be used: {}",
+                name, error
+            ))
+        };
+        let value = match self.distribution {
+            value if value == DistributionEnum::Uniform as i32 => {
+                Uniform::new_inclusive(mean - deviation, mean + deviation).sample(rng)
+            }
+            value if value == DistributionEnum::Normal as i32 => Normal::new(mean, deviation)
+                .map_err(|error| invalid_distribution(error.to_string()))?
+                .sample(rng),
+            value if value == DistributionEnum::Exponential as i32 => Exp::new(1.0 / mean)
+                .map_err(|error| invalid_distribution(error.to_string()))?
+                .sample(rng),
+            _ => return Err(invalid_distribution("it is not recognized".to_string())),
+        };
+        Ok(value.round() as i32)
+    }
+}
+
+/// Generates processes named P1, P2 and so on, that arrive in the first
+/// iteration. The sizes are at least 1 and at most the memory size, and the
+/// times are at least 1.
+pub fn generate_processes(
+    settings: &GeneratorSettings,
+    memory_size: i32,
+) -> Result<Vec<Process>, SimError> {
+    if settings.processes <= 0 {
+        return Err(SimError::InvalidConfiguration(
+            "The number of processes to generate has to be positive".to_string(),
+        ));
+    }
+    if memory_size <= 0 {
+        return Err(SimError::InvalidConfiguration(
+            "The memory size has to be positive".to_string(),
+        ));
+    }
+    settings.size.validate("size")?;
+    settings.time.validate("time")?;
+
+    let mut rng = ChaCha8Rng::seed_from_u64(settings.seed);
+    let mut processes: Vec<Process> = Vec::new();
+    for i in 1..=settings.processes {
+        let size = settings
+            .size
+            .sample(&mut rng, memory_size as f64, "size")?
+            .clamp(1, memory_size);
+        let time = settings.time.sample(&mut rng, 1.0, "time")?.max(1);
+        processes.push(Process::new(format!("P{}", i), time, size));
+    }
+    Ok(processes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_workload(processes: &[Process]) -> Vec<(String, i32, i32)> {
+        processes
+            .iter()
+            .map(|process| (process.name.clone(), process.time, process.size))
+            .collect()
+    }
+
+    #[test]
+    fn the_same_seed_generates_the_same_processes() {
+        let settings = GeneratorSettings {
+            seed: 42,
+            ..GeneratorSettings::default()
+        };
+        let processes = generate_processes(&settings, 100).unwrap();
+        assert_eq!(processes.len(), 10);
+        assert_eq!(
+            get_workload(&processes),
+            get_workload(&generate_processes(&settings, 100).unwrap())
+        );
+        assert!(processes
+            .iter()
+            .all(|process| process.time >= 1 && process.size >= 1 && process.size <= 100));
+    }
+
+    #[test]
+    fn every_distribution_can_be_sampled() {
+        for distribution in 0..=DistributionEnum::Exponential as i32 {
+            let settings = GeneratorSettings {
+                time: DistributionSettings {
+                    distribution,
+                    mean: 10.0,
+                    deviation: 2.0,
+                },
+                ..GeneratorSettings::default()
+            };
+            assert!(generate_processes(&settings, 50).is_ok());
+        }
+    }
+
+    #[test]
+    fn the_distributions_that_are_not_valid_are_an_error() {
+        let mut settings = GeneratorSettings::default();
+        settings.size.distribution = DistributionEnum::Exponential as i32 + 1;
+        assert!(generate_processes(&settings, 50).is_err());
+
+        let mut settings = GeneratorSettings::default();
+        settings.time.mean = f64::NAN;
+        assert!(generate_processes(&settings, 50).is_err());
+    }
+}
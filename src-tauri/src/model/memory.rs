@@ -1,35 +1,132 @@
-use super::process::Process;
+use super::placement::EmptyPartition;
 
 pub struct Memory {
+    pub size: i32,
     pub partitions: Vec<Partition>,
+    pub frames: Vec<Frame>,
 }
 
+#[derive(Clone)]
 pub struct Partition {
-    pub process: Box<Process>,
+    pub number: i32,
     pub size: i32,
+    pub process_id: Option<i32>,
+    /// The number of the segment of the process in the partition, only used
+    /// when the memory is segmented.
+    pub segment: Option<i32>,
+}
+
+impl Partition {
+    pub fn new(number: i32, size: i32) -> Partition {
+        Partition {
+            number,
+            size,
+            process_id: None,
+            segment: None,
+        }
+    }
+}
+
+/// A frame of the memory when it is paged, it holds a single page of a
+/// process.
+pub struct Frame {
+    pub number: i32,
+    pub process_id: Option<i32>,
+    pub page: Option<i32>,
+    // Used by the page replacement policies.
+    pub loaded_at: i32,
+    pub last_used: i32,
+    pub referenced: bool,
+}
+
+impl Frame {
+    pub fn new(number: i32) -> Frame {
+        Frame {
+            number,
+            process_id: None,
+            page: None,
+            loaded_at: 0,
+            last_used: 0,
+            referenced: false,
+        }
+    }
 }
 
 impl Memory {
-    fn new(processes: Vec<Process>) -> Memory {
-        let mut memory = Memory {
+    pub fn new(size: i32) -> Memory {
+        Memory {
+            size,
             partitions: Vec::new(),
-        };
-
-        for process in processes {
-            let boxed_process = Box::new(process);
-            memory.partitions.push(Partition {
-                size: boxed_process.size,
-                process: boxed_process,
-            });
+            frames: Vec::new(),
         }
-        return memory;
     }
 
-    fn get_total_memory_size(self) -> i32 {
+    pub fn get_total_memory_size(&self) -> i32 {
         let mut total_memory_size = 0;
         self.partitions
             .iter()
-            .for_each(|process| total_memory_size += process.size);
-        return total_memory_size;
+            .for_each(|partition| total_memory_size += partition.size);
+        total_memory_size
+    }
+
+    pub fn get_remaining_space(&self) -> i32 {
+        self.size - self.get_total_memory_size()
+    }
+
+    /// The partitions are placed one after the other, so a partition starts
+    /// where all the partitions before it end.
+    pub fn get_position_start(&self, position: usize) -> i32 {
+        let mut position_start = 0;
+        self.partitions[..position]
+            .iter()
+            .for_each(|partition| position_start += partition.size);
+        position_start
+    }
+
+    pub fn get_empty_partitions(&self) -> Vec<EmptyPartition> {
+        let mut empty_partitions = Vec::new();
+        for i in 0..self.partitions.len() {
+            if self.partitions[i].process_id.is_none() {
+                empty_partitions.push(EmptyPartition {
+                    position: i as i32,
+                    size: self.partitions[i].size,
+                });
+            }
+        }
+        empty_partitions
+    }
+
+    pub fn find_partition_with_process_id(&self, process_id: i32) -> Option<usize> {
+        self.partitions
+            .iter()
+            .position(|partition| partition.process_id == Some(process_id))
+    }
+
+    /// The number of the partition the process is in, if the process is paged
+    /// it is the number of the frame with its first page.
+    pub fn find_storage_with_process_id(&self, process_id: i32) -> Option<i32> {
+        if let Some(position) = self.find_partition_with_process_id(process_id) {
+            return Some(self.partitions[position].number);
+        }
+        self.frames
+            .iter()
+            .filter(|frame| frame.process_id == Some(process_id))
+            .min_by_key(|frame| frame.page)
+            .map(|frame| frame.number)
+    }
+
+    /// Takes the process out of every partition and frame it is in.
+    pub fn free_process(&mut self, process_id: i32) {
+        self.partitions
+            .iter_mut()
+            .filter(|partition| partition.process_id == Some(process_id))
+            .for_each(|partition| {
+                partition.process_id = None;
+                partition.segment = None;
+            });
+        self.frames
+            .iter_mut()
+            .filter(|frame| frame.process_id == Some(process_id))
+            .for_each(|frame| *frame = Frame::new(frame.number));
     }
 }
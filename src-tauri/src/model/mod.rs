@@ -3,4 +3,16 @@ pub mod memory;
 pub mod state;
 pub mod processLog;
 pub mod iterationLog;
-pub mod configuration;
\ No newline at end of file
+pub mod configuration;
+pub mod placement;
+pub mod simulator;
+pub mod event;
+pub mod allocation;
+pub mod replacement;
+pub mod scheduling;
+pub mod statistics;
+pub mod scenario;
+pub mod report;
+pub mod generator;
+pub mod comparison;
+pub mod compaction;
\ No newline at end of file
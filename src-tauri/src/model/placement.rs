@@ -0,0 +1,167 @@
+#[derive(Copy, Clone)]
+pub enum PlacementEnum {
+    FirstFit,
+    BestFit,
+    WorstFit,
+    NextFit,
+}
+
+/// An empty partition a process might enter, with its position in memory.
+pub struct EmptyPartition {
+    pub position: i32,
+    pub size: i32,
+}
+
+pub trait PlacementStrategy: Send {
+    /// Returns the index of the empty partition the process should enter, the
+    /// partitions are ordered by position. The last position is the roving
+    /// pointer used by next-fit, the other strategies ignore it.
+    fn select_partition(
+        &self,
+        partitions: &[EmptyPartition],
+        process_size: i32,
+        last_position: i32,
+    ) -> Option<usize>;
+}
+
+pub struct FirstFit;
+impl PlacementStrategy for FirstFit {
+    fn select_partition(
+        &self,
+        partitions: &[EmptyPartition],
+        process_size: i32,
+        _last_position: i32,
+    ) -> Option<usize> {
+        partitions
+            .iter()
+            .position(|partition| partition.size >= process_size)
+    }
+}
+
+pub struct BestFit;
+impl PlacementStrategy for BestFit {
+    fn select_partition(
+        &self,
+        partitions: &[EmptyPartition],
+        process_size: i32,
+        _last_position: i32,
+    ) -> Option<usize> {
+        // The smallest partition the process fits in, on a tie the first one.
+        let mut selected: Option<usize> = None;
+        for i in 0..partitions.len() {
+            if partitions[i].size >= process_size
+                && (selected.is_none() || partitions[i].size < partitions[selected.unwrap()].size)
+            {
+                selected = Some(i);
+            }
+        }
+        selected
+    }
+}
+
+pub struct WorstFit;
+impl PlacementStrategy for WorstFit {
+    fn select_partition(
+        &self,
+        partitions: &[EmptyPartition],
+        process_size: i32,
+        _last_position: i32,
+    ) -> Option<usize> {
+        // The biggest partition the process fits in, on a tie the first one.
+        let mut selected: Option<usize> = None;
+        for i in 0..partitions.len() {
+            if partitions[i].size >= process_size
+                && (selected.is_none() || partitions[i].size > partitions[selected.unwrap()].size)
+            {
+                selected = Some(i);
+            }
+        }
+        selected
+    }
+}
+
+pub struct NextFit;
+impl PlacementStrategy for NextFit {
+    fn select_partition(
+        &self,
+        partitions: &[EmptyPartition],
+        process_size: i32,
+        last_position: i32,
+    ) -> Option<usize> {
+        // Start searching from the last position a process entered and wrap
+        // around to the start of the memory if nothing was found after it.
+        let start = partitions
+            .iter()
+            .position(|partition| partition.position >= last_position)
+            .unwrap_or(0);
+        (start..partitions.len())
+            .chain(0..start)
+            .find(|&i| partitions[i].size >= process_size)
+    }
+}
+
+pub fn get_placement_strategy_from_enum(value: i32) -> Box<dyn PlacementStrategy> {
+    match value {
+        value if value == PlacementEnum::FirstFit as i32 => Box::new(FirstFit {}),
+        value if value == PlacementEnum::BestFit as i32 => Box::new(BestFit {}),
+        value if value == PlacementEnum::WorstFit as i32 => Box::new(WorstFit {}),
+        value if value == PlacementEnum::NextFit as i32 => Box::new(NextFit {}),
+        _ => panic!("Placement strategy not recognized"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_empty_partitions(sizes: &[i32]) -> Vec<EmptyPartition> {
+        sizes
+            .iter()
+            .enumerate()
+            .map(|(position, &size)| EmptyPartition {
+                position: position as i32,
+                size,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn first_fit_selects_the_first_partition_the_process_fits_in() {
+        let partitions = create_empty_partitions(&[5, 20, 10, 30]);
+        assert_eq!(FirstFit.select_partition(&partitions, 8, 0), Some(1));
+        assert_eq!(FirstFit.select_partition(&partitions, 5, 0), Some(0));
+    }
+
+    #[test]
+    fn best_fit_selects_the_smallest_partition_the_process_fits_in() {
+        let partitions = create_empty_partitions(&[5, 20, 10, 30, 10]);
+        assert_eq!(BestFit.select_partition(&partitions, 8, 0), Some(2));
+        assert_eq!(BestFit.select_partition(&partitions, 25, 0), Some(3));
+    }
+
+    #[test]
+    fn worst_fit_selects_the_biggest_partition_the_process_fits_in() {
+        let partitions = create_empty_partitions(&[5, 30, 10, 30]);
+        assert_eq!(WorstFit.select_partition(&partitions, 8, 0), Some(1));
+        assert_eq!(WorstFit.select_partition(&partitions, 1, 0), Some(1));
+    }
+
+    #[test]
+    fn next_fit_searches_from_the_last_position_and_wraps_around() {
+        let partitions = create_empty_partitions(&[5, 20, 10, 30]);
+        assert_eq!(NextFit.select_partition(&partitions, 8, 2), Some(2));
+        assert_eq!(NextFit.select_partition(&partitions, 15, 2), Some(3));
+        assert_eq!(NextFit.select_partition(&partitions, 8, 4), Some(1));
+        assert_eq!(NextFit.select_partition(&partitions, 25, 0), Some(3));
+    }
+
+    #[test]
+    fn no_partition_is_selected_when_the_process_does_not_fit() {
+        let partitions = create_empty_partitions(&[5, 20, 10]);
+        for value in 0..=PlacementEnum::NextFit as i32 {
+            let strategy = get_placement_strategy_from_enum(value);
+            assert_eq!(strategy.select_partition(&partitions, 21, 0), None);
+            assert_eq!(strategy.select_partition(&[], 1, 0), None);
+        }
+    }
+}
@@ -1,4 +1,7 @@
+use serde::{Deserialize, Serialize};
+
 use crate::database::models;
+use crate::error::SimError;
 
 use super::state;
 
@@ -7,10 +10,61 @@ pub struct Process {
     pub name: String,
     pub time: i32,
     pub size: i32,
+    pub segments: Vec<Segment>,
+    /// The pages the process references while it runs, one each time it runs.
+    pub reference_string: Vec<i32>,
+    /// The lower the number the sooner the process runs with the priority
+    /// scheduler.
+    pub priority: i32,
+    /// The iteration the process becomes available to enter the memory.
+    pub arrival: i32,
+    /// The I/O the process does while it runs, it is blocked during each burst.
+    pub io_bursts: Vec<IoBurst>,
+    /// The iterations the process has run, used to know when its I/O starts.
+    pub cpu_time: i32,
 
     pub state: Option<Box<dyn state::State>>,
 }
 
+/// The states have nothing but their number, so the state of the copy is
+/// created again from it.
+impl Clone for Process {
+    fn clone(&self) -> Process {
+        Process {
+            id: self.id,
+            name: self.name.to_string(),
+            time: self.time,
+            size: self.size,
+            segments: self.segments.clone(),
+            reference_string: self.reference_string.clone(),
+            priority: self.priority,
+            arrival: self.arrival,
+            io_bursts: self.io_bursts.clone(),
+            cpu_time: self.cpu_time,
+            state: self
+                .state
+                .as_ref()
+                .and_then(|state| state::get_state_from_enum(state.get_state_number())),
+        }
+    }
+}
+
+/// A named part of a process, like its code, data or stack, that is placed in
+/// memory on its own when the memory is segmented.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Segment {
+    pub name: String,
+    pub size: i32,
+}
+
+/// An I/O request of a process, made after it has run for the start iterations
+/// and lasting the duration iterations.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct IoBurst {
+    pub start: i32,
+    pub duration: i32,
+}
+
 impl Process {
     pub fn new(name: String, time: i32, size: i32) -> Process {
         Process {
@@ -18,28 +72,284 @@ impl Process {
             name,
             time,
             size,
+            segments: Vec::new(),
+            reference_string: Vec::new(),
+            priority: 0,
+            arrival: 1,
+            io_bursts: Vec::new(),
+            cpu_time: 0,
             state: Some(Box::new(state::Ready {})),
         }
     }
 
+    /// The segments the process declared, a process without segments is a
+    /// single segment with all of its size.
+    pub fn get_segments(&self) -> Vec<Segment> {
+        if self.segments.is_empty() {
+            return vec![Segment {
+                name: "code".to_string(),
+                size: self.size,
+            }];
+        }
+        self.segments.clone()
+    }
+
+    /// A process with segments is as big as all of them together, and every
+    /// segment needs some space.
+    pub fn set_segments(&mut self, segments: Vec<Segment>) -> Result<(), SimError> {
+        if segments.iter().any(|segment| segment.size <= 0) {
+            return Err(SimError::InvalidConfiguration(format!(
+                "The segments of process {} have to be positive",
+                self.name
+            )));
+        }
+        if !segments.is_empty() {
+            self.size = segments.iter().map(|segment| segment.size).sum();
+        }
+        self.segments = segments;
+        Ok(())
+    }
+
+    /// The reference string can only have the pages of the process with the
+    /// page size, so the size has to be set before it.
+    pub fn set_reference_string(
+        &mut self,
+        reference_string: Vec<i32>,
+        page_size: i32,
+    ) -> Result<(), SimError> {
+        check_reference_string(
+            &self.name,
+            &reference_string,
+            self.get_page_count(page_size),
+        )?;
+        self.reference_string = reference_string;
+        Ok(())
+    }
+
+    /// The reference string has to be checked again when the page size
+    /// changes.
+    pub fn check_reference_string(&self, page_size: i32) -> Result<(), SimError> {
+        check_reference_string(
+            &self.name,
+            &self.reference_string,
+            self.get_page_count(page_size),
+        )
+    }
+
+    /// The pages the process is split in, a process always has one.
+    pub fn get_page_count(&self, page_size: i32) -> i32 {
+        ((self.size + page_size - 1) / page_size).max(1)
+    }
+
+    /// The processes can arrive from the first iteration on.
+    pub fn set_arrival(&mut self, arrival: i32) -> Result<(), SimError> {
+        if arrival < 1 {
+            return Err(SimError::InvalidConfiguration(format!(
+                "Process {} has to arrive from the first iteration on",
+                self.name
+            )));
+        }
+        self.arrival = arrival;
+        Ok(())
+    }
+
+    /// The priority goes from 0, the highest, up.
+    pub fn set_priority(&mut self, priority: i32) -> Result<(), SimError> {
+        if priority < 0 {
+            return Err(SimError::InvalidConfiguration(format!(
+                "The priority of process {} can't be negative",
+                self.name
+            )));
+        }
+        self.priority = priority;
+        Ok(())
+    }
+
+    /// The I/O has to start after the process has run and before it finishes,
+    /// and it has to take some time.
+    pub fn set_io_bursts(&mut self, io_bursts: Vec<IoBurst>) -> Result<(), SimError> {
+        if io_bursts
+            .iter()
+            .any(|burst| burst.start <= 0 || burst.start >= self.time || burst.duration <= 0)
+        {
+            return Err(SimError::InvalidConfiguration(format!(
+                "The I/O bursts of process {} have to start and end while it runs",
+                self.name
+            )));
+        }
+        self.io_bursts = io_bursts;
+        Ok(())
+    }
+
     pub fn process(&mut self) {
-        println!(
-            "Processing {} with status {}",
-            self.name,
-            self.state.as_ref().unwrap().get_state_number()
-        );
         if let Some(s) = self.state.take() {
             self.state = Some(s.process(self))
         }
     }
+
+    /// Uses the CPU for an iteration when the process can run, it keeps the
+    /// CPU until the scheduler takes it away.
+    pub fn run(&mut self) {
+        let state = self.state.as_ref().unwrap().get_state_number();
+        if state == state::StateEnum::Ready as i32
+            || state == state::StateEnum::ReadyInPartition as i32
+            || state == state::StateEnum::Running as i32
+        {
+            self.state = Some(state::run(self));
+        }
+    }
+
+    /// Takes the CPU away from the process if it was running, it waits in its
+    /// partition until it is scheduled again.
+    pub fn preempt(&mut self) {
+        if self.state.as_ref().unwrap().get_state_number() == state::StateEnum::Running as i32 {
+            self.state = state::get_state_from_enum(state::StateEnum::ReadyInPartition as i32);
+        }
+    }
+}
+
+fn check_reference_string(
+    name: &str,
+    reference_string: &[i32],
+    page_count: i32,
+) -> Result<(), SimError> {
+    if reference_string
+        .iter()
+        .any(|&page| page < 0 || page >= page_count)
+    {
+        return Err(SimError::InvalidConfiguration(format!(
+            "The reference string of process {} can only have pages from 0 to {}",
+            name,
+            page_count - 1
+        )));
+    }
+    Ok(())
 }
 
-pub fn create_process_from_model(process: &models::Process) -> Process {
-    Process {
+/// The process as it was created, ready to be run from the start, as the
+/// simulations save their progress apart from it. The reference string and
+/// the I/O bursts saved in the database have to be read again, an error means
+/// they were not saved by the app.
+pub fn create_process_from_model(
+    process: &models::Process,
+    segments: &[models::Segment],
+) -> Result<Process, SimError> {
+    Ok(Process {
         id: Some(process.id),
         name: process.name.to_string(),
         time: process.time,
         size: process.size,
-        state: state::get_state_from_enum(process.state),
+        segments: segments
+            .iter()
+            .filter(|segment| segment.process_id == process.id)
+            .map(|segment| Segment {
+                name: segment.name.to_string(),
+                size: segment.size,
+            })
+            .collect(),
+        reference_string: create_reference_string_from_value(&process.reference_string)?,
+        priority: process.priority,
+        arrival: process.arrival,
+        io_bursts: create_io_bursts_from_value(&process.io_bursts)?,
+        cpu_time: 0,
+        state: Some(Box::new(state::Ready {})),
+    })
+}
+
+/// The reference string is saved as the pages separated by commas.
+pub fn get_reference_string_value(reference_string: &[i32]) -> String {
+    reference_string
+        .iter()
+        .map(|page| page.to_string())
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+pub fn create_reference_string_from_value(value: &str) -> Result<Vec<i32>, SimError> {
+    value
+        .split(',')
+        .filter(|page| !page.is_empty())
+        .map(|page| {
+            page.parse::<i32>().map_err(|_| {
+                SimError::InvalidConfiguration(format!(
+                    "The reference string {:?} is not valid",
+                    value
+                ))
+            })
+        })
+        .collect()
+}
+
+/// The I/O bursts are saved as their start and duration separated by colons,
+/// and the bursts separated by commas.
+pub fn get_io_bursts_value(io_bursts: &[IoBurst]) -> String {
+    io_bursts
+        .iter()
+        .map(|burst| format!("{}:{}", burst.start, burst.duration))
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+pub fn create_io_bursts_from_value(value: &str) -> Result<Vec<IoBurst>, SimError> {
+    value
+        .split(',')
+        .filter(|burst| !burst.is_empty())
+        .map(|burst| {
+            let (start, duration) = burst.split_once(':').unwrap_or((burst, ""));
+            match (start.parse::<i32>(), duration.parse::<i32>()) {
+                (Ok(start), Ok(duration)) => Ok(IoBurst { start, duration }),
+                _ => Err(SimError::InvalidConfiguration(format!(
+                    "The I/O bursts {:?} are not valid",
+                    value
+                ))),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_saved_values_are_read_back() {
+        let reference_string = vec![0, 3, 1];
+        let value = get_reference_string_value(&reference_string);
+        assert_eq!(
+            create_reference_string_from_value(&value).unwrap(),
+            reference_string
+        );
+
+        let value = get_io_bursts_value(&[
+            IoBurst {
+                start: 2,
+                duration: 3,
+            },
+            IoBurst {
+                start: 5,
+                duration: 1,
+            },
+        ]);
+        let io_bursts = create_io_bursts_from_value(&value).unwrap();
+        assert_eq!(io_bursts.len(), 2);
+        assert_eq!((io_bursts[1].start, io_bursts[1].duration), (5, 1));
+        assert!(create_reference_string_from_value("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn the_values_that_are_not_valid_are_an_error() {
+        assert!(create_reference_string_from_value("0,a").is_err());
+        assert!(create_io_bursts_from_value("2").is_err());
+        assert!(create_io_bursts_from_value("2:b").is_err());
+    }
+
+    #[test]
+    fn the_reference_string_only_has_the_pages_of_the_process() {
+        let mut process = Process::new("P1".to_string(), 5, 10);
+        assert!(process.set_reference_string(vec![0, 2], 4).is_ok());
+        assert!(process.set_reference_string(vec![3], 4).is_err());
+        assert!(process.set_reference_string(vec![-1], 4).is_err());
+        assert_eq!(process.reference_string, vec![0, 2]);
+        assert!(process.check_reference_string(5).is_err());
     }
 }
@@ -0,0 +1,142 @@
+#[derive(Copy, Clone)]
+pub enum ReplacementEnum {
+    Fifo,
+    Lru,
+    Clock,
+    Optimal,
+}
+
+/// A page of a process that is in a frame, with the iterations it was loaded
+/// and last used in.
+pub struct ResidentPage {
+    pub frame: i32,
+    pub page: i32,
+    pub loaded_at: i32,
+    pub last_used: i32,
+    pub referenced: bool,
+}
+
+pub trait ReplacementPolicy: Send {
+    /// Returns the index of the page that leaves memory, the pages are ordered
+    /// by frame. The future references are the pages the process is going to
+    /// reference next, only optimal looks at them.
+    fn select_victim(&self, pages: &mut [ResidentPage], future_references: &[i32]) -> usize;
+}
+
+pub struct Fifo;
+impl ReplacementPolicy for Fifo {
+    fn select_victim(&self, pages: &mut [ResidentPage], _future_references: &[i32]) -> usize {
+        // The page that has been in memory the longest, on a tie the first one.
+        let mut selected = 0;
+        for i in 0..pages.len() {
+            if pages[i].loaded_at < pages[selected].loaded_at {
+                selected = i;
+            }
+        }
+        selected
+    }
+}
+
+pub struct Lru;
+impl ReplacementPolicy for Lru {
+    fn select_victim(&self, pages: &mut [ResidentPage], _future_references: &[i32]) -> usize {
+        // The page that has not been used for the longest time.
+        let mut selected = 0;
+        for i in 0..pages.len() {
+            if pages[i].last_used < pages[selected].last_used {
+                selected = i;
+            }
+        }
+        selected
+    }
+}
+
+pub struct Clock;
+impl ReplacementPolicy for Clock {
+    fn select_victim(&self, pages: &mut [ResidentPage], _future_references: &[i32]) -> usize {
+        // The hand is right after the last page that was loaded, the pages it
+        // passes that were referenced get a second chance.
+        let mut hand = 0;
+        for i in 0..pages.len() {
+            if pages[i].loaded_at > pages[hand].loaded_at {
+                hand = i;
+            }
+        }
+        loop {
+            hand = (hand + 1) % pages.len();
+            if !pages[hand].referenced {
+                return hand;
+            }
+            pages[hand].referenced = false;
+        }
+    }
+}
+
+pub struct Optimal;
+impl ReplacementPolicy for Optimal {
+    fn select_victim(&self, pages: &mut [ResidentPage], future_references: &[i32]) -> usize {
+        // The page that is going to be used the latest, a page that is never
+        // used again is replaced first.
+        let mut selected = 0;
+        let mut selected_next_use = 0;
+        for (i, resident_page) in pages.iter().enumerate() {
+            let next_use = future_references
+                .iter()
+                .position(|&page| page == resident_page.page)
+                .unwrap_or(future_references.len());
+            if i == 0 || next_use > selected_next_use {
+                selected = i;
+                selected_next_use = next_use;
+            }
+        }
+        selected
+    }
+}
+
+pub fn get_replacement_policy_from_enum(value: i32) -> Box<dyn ReplacementPolicy> {
+    match value {
+        value if value == ReplacementEnum::Fifo as i32 => Box::new(Fifo {}),
+        value if value == ReplacementEnum::Lru as i32 => Box::new(Lru {}),
+        value if value == ReplacementEnum::Clock as i32 => Box::new(Clock {}),
+        value if value == ReplacementEnum::Optimal as i32 => Box::new(Optimal {}),
+        _ => panic!("Replacement policy not recognized"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_pages(pages: &[(i32, bool)]) -> Vec<ResidentPage> {
+        pages
+            .iter()
+            .enumerate()
+            .map(|(i, &(loaded_at, referenced))| ResidentPage {
+                frame: i as i32,
+                page: i as i32,
+                loaded_at,
+                last_used: loaded_at,
+                referenced,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn clock_clears_the_referenced_pages_it_passes() {
+        // The hand starts after the page loaded last, in the second frame.
+        let mut pages = create_pages(&[(3, false), (5, false), (1, true)]);
+        assert_eq!(Clock.select_victim(&mut pages, &[]), 0);
+        assert!(!pages[2].referenced);
+
+        let mut pages = create_pages(&[(3, true), (5, false), (1, false)]);
+        assert_eq!(Clock.select_victim(&mut pages, &[]), 2);
+        assert!(pages[0].referenced);
+    }
+
+    #[test]
+    fn clock_goes_around_when_every_page_was_referenced() {
+        let mut pages = create_pages(&[(3, true), (5, true), (1, true)]);
+        assert_eq!(Clock.select_victim(&mut pages, &[]), 2);
+        assert!(pages.iter().all(|page| !page.referenced));
+    }
+}
@@ -0,0 +1,367 @@
+use std::fs;
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::database::models;
+use crate::error::SimError;
+
+use super::simulator::{SimulationLog, Simulator, SimulatorSettings};
+use super::state::get_state_name;
+use super::statistics::{create_simulation_statistics, ProcessStatistics, SimulationStatistics};
+
+/// Everything the processor did in a simulation, to analyse it outside of the
+/// simulator.
+#[derive(Serialize)]
+pub struct SimulationReport {
+    pub settings: SimulatorSettings,
+    pub process_logs: Vec<ProcessLogRecord>,
+    pub storage_partition_logs: Vec<models::NewStoragePartitionLog>,
+    pub compaction_logs: Vec<models::CompactionLog>,
+    pub condensation_logs: Vec<models::CondensationLog>,
+    pub statistics: SimulationStatistics,
+    pub compactions: i32,
+    pub condensations: i32,
+}
+
+/// A process log with the names of the process and its state instead of their
+/// numbers.
+#[derive(Serialize)]
+pub struct ProcessLogRecord {
+    pub iteration: i32,
+    pub process_id: i32,
+    pub process_name: String,
+    pub state: String,
+    pub storage_partition_id: i32,
+    pub time_remaining: i32,
+}
+
+impl ProcessLogRecord {
+    /// The logs read from the database can have a state the app doesn't
+    /// know, which is an error instead of a name.
+    pub fn new(
+        process_log: &models::ProcessLog,
+        process_name: &str,
+    ) -> Result<ProcessLogRecord, SimError> {
+        let state = get_state_name(process_log.state).ok_or_else(|| {
+            SimError::InvalidConfiguration(format!(
+                "The state {} of process {} is not valid",
+                process_log.state, process_name
+            ))
+        })?;
+        Ok(ProcessLogRecord {
+            iteration: process_log.iteration,
+            process_id: process_log.process_id,
+            process_name: process_name.to_string(),
+            state: state.to_string(),
+            storage_partition_id: process_log.storage_partition_id,
+            time_remaining: process_log.time_remaining,
+        })
+    }
+}
+
+/// Creates the report of a simulation that ran without the database.
+pub fn create_report_from_simulator(
+    settings: &SimulatorSettings,
+    simulator: &Simulator,
+    log: &SimulationLog,
+) -> Result<SimulationReport, SimError> {
+    let get_process = |process_id: i32| {
+        simulator
+            .processes
+            .iter()
+            .find(|process| process.id == Some(process_id))
+            .unwrap()
+    };
+
+    Ok(SimulationReport {
+        settings: settings.clone(),
+        process_logs: log
+            .process_logs
+            .iter()
+            .map(|process_log| {
+                ProcessLogRecord::new(process_log, &get_process(process_log.process_id).name)
+            })
+            .collect::<Result<_, _>>()?,
+        storage_partition_logs: log.storage_partition_logs.clone(),
+        compaction_logs: log.compaction_logs.clone(),
+        condensation_logs: log.condensation_logs.clone(),
+        statistics: create_simulation_statistics(
+            log.finished_processes
+                .iter()
+                .map(|finished_process| {
+                    let process = get_process(finished_process.process_id);
+                    ProcessStatistics::new(
+                        finished_process.process_id,
+                        process.name.to_string(),
+                        process.arrival,
+                        finished_process.admission_iteration,
+                        finished_process.first_run_iteration,
+                        finished_process.finish_iteration,
+                        finished_process.waiting_time,
+                    )
+                })
+                .collect(),
+        ),
+        compactions: simulator.compactions,
+        condensations: simulator.condensations,
+    })
+}
+
+/// Writes the report as JSON if the path has the .json extension. Otherwise
+/// the path is a directory, and every log is written to its own CSV file in
+/// it.
+pub fn write_report(path: &str, report: &SimulationReport) -> Result<(), SimError> {
+    let is_json = Path::new(path)
+        .extension()
+        .map(|extension| extension == "json")
+        .unwrap_or(false);
+    if is_json {
+        let contents = serde_json::to_string_pretty(report).map_err(|error| {
+            SimError::File(format!("Could not create report {}: {}", path, error))
+        })?;
+        return fs::write(path, contents).map_err(|error| {
+            SimError::File(format!("Could not write report {}: {}", path, error))
+        });
+    }
+
+    fs::create_dir_all(path).map_err(|error| {
+        SimError::File(format!("Could not create directory {}: {}", path, error))
+    })?;
+    let directory = Path::new(path);
+    write_csv(&directory.join("process_logs.csv"), &report.process_logs)?;
+    write_csv(
+        &directory.join("storage_partition_logs.csv"),
+        &report.storage_partition_logs,
+    )?;
+    write_csv(
+        &directory.join("compaction_logs.csv"),
+        &report.compaction_logs,
+    )?;
+    write_csv(
+        &directory.join("condensation_logs.csv"),
+        &report.condensation_logs,
+    )?;
+    write_csv(
+        &directory.join("finished_processes.csv"),
+        &report.statistics.processes,
+    )
+}
+
+/// A row of a CSV file.
+trait CsvRecord {
+    fn get_header() -> Vec<&'static str>;
+    fn get_values(&self) -> Vec<String>;
+}
+
+impl CsvRecord for ProcessLogRecord {
+    fn get_header() -> Vec<&'static str> {
+        vec![
+            "iteration",
+            "process_id",
+            "process_name",
+            "state",
+            "storage_partition_id",
+            "time_remaining",
+        ]
+    }
+
+    fn get_values(&self) -> Vec<String> {
+        vec![
+            self.iteration.to_string(),
+            self.process_id.to_string(),
+            self.process_name.to_string(),
+            self.state.to_string(),
+            self.storage_partition_id.to_string(),
+            self.time_remaining.to_string(),
+        ]
+    }
+}
+
+impl CsvRecord for models::NewStoragePartitionLog {
+    fn get_header() -> Vec<&'static str> {
+        vec![
+            "iteration",
+            "storage_partition_id",
+            "position",
+            "size",
+            "internal_fragmentation",
+        ]
+    }
+
+    fn get_values(&self) -> Vec<String> {
+        vec![
+            self.iteration.to_string(),
+            self.storage_partition_id.to_string(),
+            self.position.to_string(),
+            self.size.to_string(),
+            self.internal_fragmentation.to_string(),
+        ]
+    }
+}
+
+impl CsvRecord for models::CompactionLog {
+    fn get_header() -> Vec<&'static str> {
+        vec![
+            "iteration",
+            "partition",
+            "previous_position",
+            "final_position",
+        ]
+    }
+
+    fn get_values(&self) -> Vec<String> {
+        vec![
+            self.iteration.to_string(),
+            self.partition.to_string(),
+            self.previous_position.to_string(),
+            self.final_position.to_string(),
+        ]
+    }
+}
+
+impl CsvRecord for models::CondensationLog {
+    fn get_header() -> Vec<&'static str> {
+        vec![
+            "partition",
+            "partition_size",
+            "new_partition",
+            "new_partition_size",
+        ]
+    }
+
+    fn get_values(&self) -> Vec<String> {
+        vec![
+            self.partition.to_string(),
+            self.partition_size.to_string(),
+            self.new_partition.to_string(),
+            self.new_partition_size.to_string(),
+        ]
+    }
+}
+
+impl CsvRecord for ProcessStatistics {
+    fn get_header() -> Vec<&'static str> {
+        vec![
+            "process_id",
+            "process_name",
+            "arrival",
+            "admission_iteration",
+            "first_run_iteration",
+            "finish_iteration",
+            "turnaround_time",
+            "waiting_time",
+            "response_time",
+        ]
+    }
+
+    fn get_values(&self) -> Vec<String> {
+        vec![
+            self.process_id.to_string(),
+            self.name.to_string(),
+            self.arrival.to_string(),
+            self.admission_iteration.to_string(),
+            self.first_run_iteration.to_string(),
+            self.finish_iteration.to_string(),
+            self.turnaround_time.to_string(),
+            self.waiting_time.to_string(),
+            self.response_time.to_string(),
+        ]
+    }
+}
+
+fn write_csv<T: CsvRecord>(path: &Path, records: &[T]) -> Result<(), SimError> {
+    let mut contents = T::get_header().join(",");
+    contents.push('\n');
+    for record in records {
+        let values: Vec<String> = record
+            .get_values()
+            .iter()
+            .map(|value| get_csv_field(value))
+            .collect();
+        contents.push_str(&values.join(","));
+        contents.push('\n');
+    }
+    fs::write(path, contents)
+        .map_err(|error| SimError::File(format!("Could not write {}: {}", path.display(), error)))
+}
+
+/// The process names are chosen by the user, so they can have commas or
+/// quotes that have to be escaped.
+fn get_csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::process::Process;
+    use crate::model::state::StateEnum;
+
+    fn create_process_log(state: i32) -> models::ProcessLog {
+        models::ProcessLog {
+            id: 1,
+            iteration: 2,
+            process_id: 1,
+            storage_partition_id: 1,
+            time_remaining: 3,
+            state,
+        }
+    }
+
+    #[test]
+    fn the_process_logs_have_the_name_of_the_state() {
+        let record =
+            ProcessLogRecord::new(&create_process_log(StateEnum::Running as i32), "P1").unwrap();
+        assert_eq!(record.process_name, "P1");
+        assert_eq!(record.state, "Running");
+    }
+
+    #[test]
+    fn a_state_that_is_not_known_is_an_error() {
+        let error = ProcessLogRecord::new(&create_process_log(99), "P1")
+            .err()
+            .unwrap();
+        assert_eq!(error.get_kind(), "InvalidConfiguration");
+    }
+
+    #[test]
+    fn the_fields_with_commas_or_quotes_are_escaped() {
+        assert_eq!(get_csv_field("P1"), "P1");
+        assert_eq!(get_csv_field("P1, P2"), "\"P1, P2\"");
+        assert_eq!(get_csv_field("the \"big\" one"), "\"the \"\"big\"\" one\"");
+        assert_eq!(get_csv_field("P1\nP2"), "\"P1\nP2\"");
+    }
+
+    #[test]
+    fn every_log_is_written_to_its_own_csv_file() {
+        let settings = SimulatorSettings::default();
+        let processes = vec![Process::new("P1, \"big\"".to_string(), 1, 10)];
+        let mut simulator = Simulator::new(&settings, processes);
+        simulator.run();
+        let log = simulator.take_log();
+        let report = create_report_from_simulator(&settings, &simulator, &log).unwrap();
+
+        let directory = std::env::temp_dir().join(format!("memsim-report-{}", std::process::id()));
+        write_report(directory.to_str().unwrap(), &report).unwrap();
+        let process_logs = fs::read_to_string(directory.join("process_logs.csv")).unwrap();
+        let finished_processes =
+            fs::read_to_string(directory.join("finished_processes.csv")).unwrap();
+        fs::remove_dir_all(&directory).unwrap();
+
+        let lines: Vec<&str> = process_logs.lines().collect();
+        assert_eq!(lines.len(), report.process_logs.len() + 1);
+        assert_eq!(
+            lines[0],
+            "iteration,process_id,process_name,state,storage_partition_id,time_remaining"
+        );
+        assert!(lines[1..]
+            .iter()
+            .all(|line| line.contains(",\"P1, \"\"big\"\"\",")));
+        assert_eq!(finished_processes.lines().count(), 2);
+    }
+}
@@ -0,0 +1,264 @@
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::SimError;
+
+use super::generator::{generate_processes, GeneratorSettings};
+use super::process::{IoBurst, Process, Segment};
+use super::simulator::SimulatorSettings;
+
+/// The processes to run and the settings to run them with, the settings that
+/// are not in the scenario have the values they have by default. The random
+/// processes of the generator go after the ones in the list.
+#[derive(Serialize, Deserialize)]
+pub struct Scenario {
+    #[serde(default)]
+    pub settings: SimulatorSettings,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generator: Option<GeneratorSettings>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub processes: Vec<ScenarioProcess>,
+}
+
+/// A process of the scenario, only its name, time and size are needed. The
+/// lists of tables go last so the scenario can be written as TOML, and they
+/// are left out when they are empty, as an empty list can't go after a table.
+#[derive(Serialize, Deserialize)]
+pub struct ScenarioProcess {
+    pub name: String,
+    pub time: i32,
+    pub size: i32,
+    #[serde(default)]
+    pub reference_string: Vec<i32>,
+    #[serde(default = "get_default_arrival")]
+    pub arrival: i32,
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub segments: Vec<Segment>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub io_bursts: Vec<IoBurst>,
+}
+
+impl Scenario {
+    /// The processes of the scenario, the error says which process is not
+    /// valid and why.
+    pub fn create_processes(&self) -> Result<Vec<Process>, SimError> {
+        let mut processes: Vec<Process> = Vec::new();
+        for scenario_process in &self.processes {
+            let name = &scenario_process.name;
+            if processes.iter().any(|process| &process.name == name) {
+                return Err(SimError::ConstraintViolation(format!(
+                    "There is more than one process named {}",
+                    name
+                )));
+            }
+            if scenario_process.time <= 0 {
+                return Err(SimError::InvalidConfiguration(format!(
+                    "The time of process {} has to be positive",
+                    name
+                )));
+            }
+            if scenario_process.size <= 0 && scenario_process.segments.is_empty() {
+                return Err(SimError::InvalidConfiguration(format!(
+                    "The size of process {} has to be positive",
+                    name
+                )));
+            }
+            let mut process = Process::new(
+                name.to_string(),
+                scenario_process.time,
+                scenario_process.size,
+            );
+            process.set_segments(scenario_process.segments.clone())?;
+            process.set_reference_string(
+                scenario_process.reference_string.clone(),
+                self.settings.page_size,
+            )?;
+            process.set_arrival(scenario_process.arrival)?;
+            process.set_priority(scenario_process.priority)?;
+            process.set_io_bursts(scenario_process.io_bursts.clone())?;
+            processes.push(process);
+        }
+        if let Some(generator) = &self.generator {
+            for process in generate_processes(generator, self.settings.memory_size)? {
+                if processes.iter().any(|other| other.name == process.name) {
+                    return Err(SimError::ConstraintViolation(format!(
+                        "There is more than one process named {}",
+                        process.name
+                    )));
+                }
+                processes.push(process);
+            }
+        }
+        Ok(processes)
+    }
+}
+
+pub fn create_scenario_process(process: &Process) -> ScenarioProcess {
+    ScenarioProcess {
+        name: process.name.to_string(),
+        time: process.time,
+        size: process.size,
+        reference_string: process.reference_string.clone(),
+        arrival: process.arrival,
+        priority: process.priority,
+        segments: process.segments.clone(),
+        io_bursts: process.io_bursts.clone(),
+    }
+}
+
+/// Reads a scenario saved as TOML if the file has the .toml extension, or as
+/// JSON otherwise, and checks its settings are valid.
+pub fn read_scenario(path: &str) -> Result<Scenario, SimError> {
+    let contents = fs::read_to_string(path)
+        .map_err(|error| SimError::File(format!("Could not read scenario {}: {}", path, error)))?;
+    let scenario: Scenario = if is_toml(path) {
+        toml::from_str(&contents).map_err(|error| error.to_string())
+    } else {
+        serde_json::from_str(&contents).map_err(|error| error.to_string())
+    }
+    .map_err(|error| {
+        SimError::InvalidConfiguration(format!("Could not parse scenario {}: {}", path, error))
+    })?;
+    scenario.settings.validate()?;
+    Ok(scenario)
+}
+
+/// Writes the scenario as TOML if the file has the .toml extension, or as JSON
+/// otherwise.
+pub fn write_scenario(path: &str, scenario: &Scenario) -> Result<(), SimError> {
+    let contents = if is_toml(path) {
+        toml::to_string_pretty(scenario).map_err(|error| error.to_string())
+    } else {
+        serde_json::to_string_pretty(scenario).map_err(|error| error.to_string())
+    }
+    .map_err(|error| SimError::File(format!("Could not create scenario {}: {}", path, error)))?;
+    fs::write(path, contents)
+        .map_err(|error| SimError::File(format!("Could not write scenario {}: {}", path, error)))
+}
+
+fn is_toml(path: &str) -> bool {
+    Path::new(path)
+        .extension()
+        .map(|extension| extension == "toml")
+        .unwrap_or(false)
+}
+
+fn get_default_arrival() -> i32 {
+    1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_scenario() -> Scenario {
+        Scenario {
+            settings: SimulatorSettings {
+                memory_size: 64,
+                cpus: 2,
+                ..SimulatorSettings::default()
+            },
+            generator: None,
+            processes: vec![
+                ScenarioProcess {
+                    name: "P1".to_string(),
+                    time: 5,
+                    size: 8,
+                    reference_string: vec![0, 1, 0],
+                    arrival: 2,
+                    priority: 1,
+                    segments: Vec::new(),
+                    io_bursts: vec![IoBurst {
+                        start: 2,
+                        duration: 3,
+                    }],
+                },
+                ScenarioProcess {
+                    name: "P2".to_string(),
+                    time: 3,
+                    size: 0,
+                    reference_string: Vec::new(),
+                    arrival: 1,
+                    priority: 0,
+                    segments: vec![
+                        Segment {
+                            name: "code".to_string(),
+                            size: 4,
+                        },
+                        Segment {
+                            name: "data".to_string(),
+                            size: 6,
+                        },
+                    ],
+                    io_bursts: Vec::new(),
+                },
+            ],
+        }
+    }
+
+    fn read_written_scenario(file_name: &str) -> Scenario {
+        let path = std::env::temp_dir().join(file_name);
+        let path = path.to_str().unwrap();
+        write_scenario(path, &create_scenario()).unwrap();
+        let scenario = read_scenario(path).unwrap();
+        fs::remove_file(path).unwrap();
+        scenario
+    }
+
+    fn check_processes(scenario: &Scenario) {
+        let processes = scenario.create_processes().unwrap();
+        assert_eq!(processes.len(), 2);
+        assert_eq!(processes[0].name, "P1");
+        assert_eq!(processes[0].time, 5);
+        assert_eq!(processes[0].size, 8);
+        assert_eq!(processes[0].reference_string, vec![0, 1, 0]);
+        assert_eq!(processes[0].arrival, 2);
+        assert_eq!(processes[0].priority, 1);
+        assert_eq!(processes[0].io_bursts[0].start, 2);
+        assert_eq!(processes[0].io_bursts[0].duration, 3);
+        assert_eq!(processes[1].size, 10);
+        assert_eq!(processes[1].segments[1].name, "data");
+    }
+
+    #[test]
+    fn a_toml_scenario_is_read_as_it_was_written() {
+        let scenario = read_written_scenario("memsim_round_trip_test.toml");
+        assert_eq!(scenario.settings.memory_size, 64);
+        assert_eq!(scenario.settings.cpus, 2);
+        check_processes(&scenario);
+    }
+
+    #[test]
+    fn a_json_scenario_is_read_as_it_was_written() {
+        let scenario = read_written_scenario("memsim_round_trip_test.json");
+        assert_eq!(scenario.settings.memory_size, 64);
+        assert_eq!(scenario.settings.cpus, 2);
+        check_processes(&scenario);
+    }
+
+    #[test]
+    fn the_exported_processes_are_the_same_as_the_imported_ones() {
+        let processes = create_scenario().create_processes().unwrap();
+        let scenario = Scenario {
+            settings: SimulatorSettings::default(),
+            generator: None,
+            processes: processes.iter().map(create_scenario_process).collect(),
+        };
+        check_processes(&scenario);
+    }
+
+    #[test]
+    fn the_processes_without_time_or_with_the_same_name_are_not_valid() {
+        let mut scenario = create_scenario();
+        scenario.processes[1].time = 0;
+        assert!(scenario.create_processes().is_err());
+
+        let mut scenario = create_scenario();
+        scenario.processes[1].name = "P1".to_string();
+        assert!(scenario.create_processes().is_err());
+    }
+}
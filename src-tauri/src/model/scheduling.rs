@@ -0,0 +1,186 @@
+#[derive(Copy, Clone)]
+pub enum SchedulingEnum {
+    Fcfs,
+    RoundRobin,
+    Sjf,
+    Srtf,
+    Priority,
+}
+
+/// A resident process waiting for a CPU or using one.
+pub struct SchedulableProcess {
+    pub time: i32,
+    pub priority: i32,
+    /// If the process had a CPU in the last iteration.
+    pub is_running: bool,
+    /// The iterations the process has had its CPU without leaving it.
+    pub quantum_used: i32,
+}
+
+pub trait Scheduler: Send {
+    /// Returns the indexes of the processes that get a CPU this iteration, at
+    /// most one for every CPU. The processes are ordered by the time they
+    /// entered the ready queue.
+    fn select_processes(&self, processes: &[SchedulableProcess], cpus: usize) -> Vec<usize>;
+}
+
+pub struct Fcfs;
+impl Scheduler for Fcfs {
+    fn select_processes(&self, processes: &[SchedulableProcess], cpus: usize) -> Vec<usize> {
+        // The running processes keep their CPU until they finish.
+        let mut selected: Vec<usize> = (0..processes.len())
+            .filter(|&i| processes[i].is_running)
+            .collect();
+        selected.extend((0..processes.len()).filter(|&i| !processes[i].is_running));
+        selected.truncate(cpus);
+        selected
+    }
+}
+
+pub struct RoundRobin {
+    pub quantum: i32,
+}
+impl Scheduler for RoundRobin {
+    fn select_processes(&self, processes: &[SchedulableProcess], cpus: usize) -> Vec<usize> {
+        // The running processes keep their CPU until their quantum is used,
+        // then they go after every process that is waiting.
+        let has_quantum_left = |i: &usize| processes[*i].quantum_used < self.quantum;
+        let mut selected: Vec<usize> = (0..processes.len())
+            .filter(|i| processes[*i].is_running && has_quantum_left(i))
+            .collect();
+        selected.extend((0..processes.len()).filter(|&i| !processes[i].is_running));
+        selected.extend(
+            (0..processes.len()).filter(|i| processes[*i].is_running && !has_quantum_left(i)),
+        );
+        selected.truncate(cpus);
+        selected
+    }
+}
+
+pub struct Sjf;
+impl Scheduler for Sjf {
+    fn select_processes(&self, processes: &[SchedulableProcess], cpus: usize) -> Vec<usize> {
+        // The running processes keep their CPU, the free ones go to the
+        // shortest waiting processes, on a tie the first one.
+        let mut waiting: Vec<usize> = (0..processes.len())
+            .filter(|&i| !processes[i].is_running)
+            .collect();
+        waiting.sort_by_key(|&i| processes[i].time);
+        let mut selected: Vec<usize> = (0..processes.len())
+            .filter(|&i| processes[i].is_running)
+            .collect();
+        selected.extend(waiting);
+        selected.truncate(cpus);
+        selected
+    }
+}
+
+pub struct Srtf;
+impl Scheduler for Srtf {
+    fn select_processes(&self, processes: &[SchedulableProcess], cpus: usize) -> Vec<usize> {
+        // The processes with the least time left, a running process loses its
+        // CPU when a shorter one is waiting.
+        let mut selected: Vec<usize> = (0..processes.len()).collect();
+        selected.sort_by_key(|&i| processes[i].time);
+        selected.truncate(cpus);
+        selected
+    }
+}
+
+pub struct Priority;
+impl Scheduler for Priority {
+    fn select_processes(&self, processes: &[SchedulableProcess], cpus: usize) -> Vec<usize> {
+        // The lower the number the higher the priority, a running process loses
+        // its CPU when one with a higher priority is waiting.
+        let mut selected: Vec<usize> = (0..processes.len()).collect();
+        selected.sort_by_key(|&i| processes[i].priority);
+        selected.truncate(cpus);
+        selected
+    }
+}
+
+pub fn get_scheduler_from_enum(value: i32, quantum: i32) -> Box<dyn Scheduler> {
+    match value {
+        value if value == SchedulingEnum::Fcfs as i32 => Box::new(Fcfs {}),
+        value if value == SchedulingEnum::RoundRobin as i32 => Box::new(RoundRobin { quantum }),
+        value if value == SchedulingEnum::Sjf as i32 => Box::new(Sjf {}),
+        value if value == SchedulingEnum::Srtf as i32 => Box::new(Srtf {}),
+        value if value == SchedulingEnum::Priority as i32 => Box::new(Priority {}),
+        _ => panic!("Scheduling policy not recognized"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_process(time: i32, priority: i32, is_running: bool) -> SchedulableProcess {
+        SchedulableProcess {
+            time,
+            priority,
+            is_running,
+            quantum_used: if is_running { 1 } else { 0 },
+        }
+    }
+
+    #[test]
+    fn fcfs_keeps_the_running_processes_until_they_finish() {
+        let processes = vec![
+            create_process(5, 0, false),
+            create_process(9, 0, true),
+            create_process(1, 0, false),
+        ];
+        assert_eq!(Fcfs.select_processes(&processes, 1), vec![1]);
+        assert_eq!(Fcfs.select_processes(&processes, 2), vec![1, 0]);
+        assert_eq!(Fcfs.select_processes(&processes, 5), vec![1, 0, 2]);
+    }
+
+    #[test]
+    fn round_robin_moves_the_running_processes_back_after_their_quantum() {
+        let processes = vec![
+            create_process(5, 0, true),
+            create_process(9, 0, false),
+            create_process(1, 0, false),
+        ];
+        let scheduler = RoundRobin { quantum: 2 };
+        assert_eq!(scheduler.select_processes(&processes, 1), vec![0]);
+        let scheduler = RoundRobin { quantum: 1 };
+        assert_eq!(scheduler.select_processes(&processes, 1), vec![1]);
+        assert_eq!(scheduler.select_processes(&processes, 3), vec![1, 2, 0]);
+    }
+
+    #[test]
+    fn sjf_gives_the_free_cpus_to_the_shortest_waiting_processes() {
+        let processes = vec![
+            create_process(5, 0, false),
+            create_process(9, 0, true),
+            create_process(1, 0, false),
+            create_process(1, 0, false),
+        ];
+        assert_eq!(Sjf.select_processes(&processes, 1), vec![1]);
+        assert_eq!(Sjf.select_processes(&processes, 3), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn srtf_preempts_the_running_process_for_a_shorter_one() {
+        let processes = vec![
+            create_process(5, 0, false),
+            create_process(9, 0, true),
+            create_process(1, 0, false),
+        ];
+        assert_eq!(Srtf.select_processes(&processes, 1), vec![2]);
+        assert_eq!(Srtf.select_processes(&processes, 2), vec![2, 0]);
+    }
+
+    #[test]
+    fn priority_runs_the_lowest_numbers_first() {
+        let processes = vec![
+            create_process(5, 3, true),
+            create_process(9, 1, false),
+            create_process(1, 1, false),
+        ];
+        let scheduler = get_scheduler_from_enum(SchedulingEnum::Priority as i32, 1);
+        assert_eq!(scheduler.select_processes(&processes, 1), vec![1]);
+        assert_eq!(scheduler.select_processes(&processes, 3), vec![1, 2, 0]);
+    }
+}
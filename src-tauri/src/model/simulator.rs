@@ -0,0 +1,1158 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::database::models;
+use crate::error::SimError;
+
+use super::allocation::{get_allocation_from_enum, AllocationEnum};
+use super::compaction::{get_compaction_from_enum, CompactionEnum};
+use super::event::{IterationComplete, PartitionSplit, SimulatorEvent};
+use super::memory::{Memory, Partition};
+use super::placement::{get_placement_strategy_from_enum, PlacementEnum, PlacementStrategy};
+use super::process::Process;
+use super::replacement::{get_replacement_policy_from_enum, ReplacementEnum, ReplacementPolicy};
+use super::scheduling::{get_scheduler_from_enum, Scheduler, SchedulingEnum};
+use super::state::StateEnum;
+
+mod buddy;
+mod demand_paging;
+mod fixed;
+mod fragmentation;
+mod io;
+mod paging;
+mod scheduling;
+mod segmentation;
+mod swapping;
+
+/// The settings a simulation runs with, the strategies are saved as the
+/// numbers of their enums, the same way they are in the configuration.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SimulatorSettings {
+    pub memory_size: i32,
+    pub placement_strategy: i32,
+    pub allocation_mode: i32,
+    /// The partition table used when the memory has fixed partitions.
+    pub fixed_partition_sizes: Vec<i32>,
+    pub page_size: i32,
+    pub replacement_policy: i32,
+    /// The most frames a process can have with virtual memory.
+    pub frames_per_process: i32,
+    /// If resident processes can be swapped out to let waiting processes in.
+    pub swapping: bool,
+    /// The iterations it takes to swap a process in or out, at least one so
+    /// the process leaves the memory after the iteration it was chosen in.
+    pub swap_time: i32,
+    pub scheduling_policy: i32,
+    /// The iterations a process runs before leaving its CPU with round robin.
+    pub quantum: i32,
+    /// The most processes that run at the same time, with 0 every resident
+    /// process runs and the scheduling policy is not used.
+    pub cpus: i32,
+    /// The iterations a process has to wait for its priority to go up by one,
+    /// the processes don't age with 0.
+    pub aging: i32,
+    /// The most blocked processes that do I/O at the same time.
+    pub io_devices: i32,
+    /// When the memory is compacted with dynamic partitions or segmentation.
+    pub compaction_policy: i32,
+    /// The percentage of external fragmentation the memory is compacted at
+    /// with the threshold policy.
+    pub compaction_threshold: i32,
+}
+
+/// The same settings the configuration has when the user has not chosen any.
+impl Default for SimulatorSettings {
+    fn default() -> SimulatorSettings {
+        SimulatorSettings {
+            memory_size: 50,
+            placement_strategy: PlacementEnum::FirstFit as i32,
+            allocation_mode: AllocationEnum::DynamicPartitions as i32,
+            fixed_partition_sizes: Vec::new(),
+            page_size: 4,
+            replacement_policy: ReplacementEnum::Fifo as i32,
+            frames_per_process: 3,
+            swapping: false,
+            swap_time: 1,
+            scheduling_policy: SchedulingEnum::RoundRobin as i32,
+            quantum: 1,
+            cpus: 0,
+            aging: 0,
+            io_devices: 1,
+            compaction_policy: CompactionEnum::Always as i32,
+            compaction_threshold: 50,
+        }
+    }
+}
+
+impl SimulatorSettings {
+    /// The error says which setting is not valid.
+    pub fn validate(&self) -> Result<(), SimError> {
+        if self.memory_size <= 0 {
+            return Err(SimError::InvalidConfiguration(
+                "The memory size has to be positive".to_string(),
+            ));
+        }
+        if self.placement_strategy < 0 || self.placement_strategy > PlacementEnum::NextFit as i32 {
+            return Err(SimError::InvalidConfiguration(
+                "The placement strategy is not recognized".to_string(),
+            ));
+        }
+        if self.allocation_mode < 0 || self.allocation_mode > AllocationEnum::VirtualMemory as i32 {
+            return Err(SimError::InvalidConfiguration(
+                "The allocation mode is not recognized".to_string(),
+            ));
+        }
+        if self.fixed_partition_sizes.iter().any(|&size| size <= 0)
+            || self.fixed_partition_sizes.iter().sum::<i32>() > self.memory_size
+        {
+            return Err(SimError::InvalidConfiguration(
+                "The fixed partitions have to be positive and fit in memory".to_string(),
+            ));
+        }
+        if self.page_size <= 0 {
+            return Err(SimError::InvalidConfiguration(
+                "The page size has to be positive".to_string(),
+            ));
+        }
+        if self.replacement_policy < 0 || self.replacement_policy > ReplacementEnum::Optimal as i32
+        {
+            return Err(SimError::InvalidConfiguration(
+                "The replacement policy is not recognized".to_string(),
+            ));
+        }
+        if self.frames_per_process <= 0 {
+            return Err(SimError::InvalidConfiguration(
+                "The frames per process have to be positive".to_string(),
+            ));
+        }
+        if self.swap_time < 1 {
+            return Err(SimError::InvalidConfiguration(
+                "The swap time has to be at least 1".to_string(),
+            ));
+        }
+        if self.scheduling_policy < 0 || self.scheduling_policy > SchedulingEnum::Priority as i32 {
+            return Err(SimError::InvalidConfiguration(
+                "The scheduling policy is not recognized".to_string(),
+            ));
+        }
+        if self.quantum <= 0 {
+            return Err(SimError::InvalidConfiguration(
+                "The quantum has to be positive".to_string(),
+            ));
+        }
+        if self.cpus < 0 {
+            return Err(SimError::InvalidConfiguration(
+                "The number of CPUs can't be negative".to_string(),
+            ));
+        }
+        if self.aging < 0 {
+            return Err(SimError::InvalidConfiguration(
+                "The aging can't be negative".to_string(),
+            ));
+        }
+        if self.io_devices <= 0 {
+            return Err(SimError::InvalidConfiguration(
+                "The number of I/O devices has to be positive".to_string(),
+            ));
+        }
+        if self.compaction_policy < 0 || self.compaction_policy > CompactionEnum::Never as i32 {
+            return Err(SimError::InvalidConfiguration(
+                "The compaction policy is not recognized".to_string(),
+            ));
+        }
+        if self.compaction_threshold < 0 || self.compaction_threshold > 100 {
+            return Err(SimError::InvalidConfiguration(
+                "The compaction threshold has to be a percentage".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Every process has to fit in the space the allocation mode can give to a
+    /// single process, except with virtual memory as only the pages being used
+    /// are loaded, but then the reference strings can only have the pages of
+    /// the processes and the frames they reserve have to be in the memory. The
+    /// settings have to be valid.
+    pub fn check_processes(&self, processes: &[Process]) -> Result<(), SimError> {
+        let allocation_mode = get_allocation_from_enum(self.allocation_mode);
+        let is_paged = matches!(
+            allocation_mode,
+            AllocationEnum::Paging | AllocationEnum::VirtualMemory
+        );
+        if is_paged && self.page_size > self.memory_size {
+            return Err(SimError::InvalidConfiguration(format!(
+                "The page size of {} doesn't fit in a memory of {}",
+                self.page_size, self.memory_size
+            )));
+        }
+
+        let capacity = match allocation_mode {
+            AllocationEnum::VirtualMemory => {
+                let frame_count = self.memory_size / self.page_size;
+                return processes.iter().try_for_each(|process| {
+                    process.check_reference_string(self.page_size)?;
+                    let frames = process
+                        .get_page_count(self.page_size)
+                        .min(self.frames_per_process);
+                    if frames > frame_count {
+                        return Err(SimError::InvalidConfiguration(format!(
+                            "Process {} reserves {} frames but the memory only has {}",
+                            process.name, frames, frame_count
+                        )));
+                    }
+                    Ok(())
+                });
+            }
+            // The segments are placed on their own, so they only have to fit
+            // in the memory all together.
+            AllocationEnum::DynamicPartitions | AllocationEnum::Segmentation => self.memory_size,
+            // The space left after the table is one more partition.
+            AllocationEnum::FixedPartitions => {
+                let remaining_space =
+                    self.memory_size - self.fixed_partition_sizes.iter().sum::<i32>();
+                self.fixed_partition_sizes
+                    .iter()
+                    .copied()
+                    .chain(std::iter::once(remaining_space))
+                    .max()
+                    .unwrap()
+            }
+            AllocationEnum::BuddySystem => buddy::get_buddy_memory_size(self.memory_size),
+            // Only whole frames are used, the space left over is not.
+            AllocationEnum::Paging => self.memory_size / self.page_size * self.page_size,
+        };
+        match processes.iter().find(|process| process.size > capacity) {
+            Some(process) => Err(SimError::MemoryTooSmall {
+                process: process.name.clone(),
+                size: process.size,
+                memory_size: capacity,
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Everything logged by the simulator that has not been taken out yet, this is
+/// what gets saved into the database.
+#[derive(Default)]
+pub struct SimulationLog {
+    pub iterations: Vec<models::IterationLog>,
+    pub process_logs: Vec<models::ProcessLog>,
+    pub storage_partition_logs: Vec<models::NewStoragePartitionLog>,
+    pub compaction_logs: Vec<models::CompactionLog>,
+    pub condensation_logs: Vec<models::CondensationLog>,
+    pub finished_processes: Vec<models::NewFinishedProcess>,
+    pub frame_logs: Vec<models::NewFrameLog>,
+    pub page_fault_logs: Vec<models::PageFaultLog>,
+    pub swap_logs: Vec<models::SwapLog>,
+    pub fragmentation_logs: Vec<models::FragmentationLog>,
+}
+
+/// Runs the processor entirely in memory, without touching the database.
+pub struct Simulator {
+    pub memory: Memory,
+    pub processes: Vec<Process>,
+    pub iteration: i32,
+    pub compactions: i32,
+    pub condensations: i32,
+    pub partition_consecutive_number: i32,
+    pub next_fit_position: i32,
+    pub has_finished: bool,
+
+    placement_strategy: Box<dyn PlacementStrategy>,
+    allocation_mode: AllocationEnum,
+    fixed_partition_sizes: Vec<i32>,
+    page_size: i32,
+    replacement_policy: Box<dyn ReplacementPolicy>,
+    frames_per_process: i32,
+    // The position in the reference string and the page faults of every
+    // process, only used with virtual memory.
+    reference_positions: HashMap<i32, usize>,
+    page_faults: HashMap<i32, i32>,
+    swapping: bool,
+    swap_time: i32,
+    // The iterations left for the swaps in progress of every process.
+    swap_times: HashMap<i32, i32>,
+    scheduler: Box<dyn Scheduler>,
+    cpus: i32,
+    // The resident processes that can run, in the order they wait for a CPU,
+    // and the iterations in a row each running process has had its CPU.
+    ready_queue: Vec<i32>,
+    quantum_used: HashMap<i32, i32>,
+    aging: i32,
+    // The iterations every process has waited to enter the memory or to get a
+    // CPU since it last ran.
+    waiting_times: HashMap<i32, i32>,
+    // The iterations every process entered the memory for the first time, ran
+    // for the first time and has waited in total, for the statistics.
+    admission_iterations: HashMap<i32, i32>,
+    first_run_iterations: HashMap<i32, i32>,
+    total_waiting_times: HashMap<i32, i32>,
+    io_devices: i32,
+    // The blocked processes in the order they wait for a device, and the
+    // iterations of I/O each of them has left.
+    io_queue: Vec<i32>,
+    io_times: HashMap<i32, i32>,
+    compaction_policy: CompactionEnum,
+    compaction_threshold: i32,
+    log: SimulationLog,
+    events: Vec<SimulatorEvent>,
+    // The logs are given their ids here, so the events sent before saving them
+    // have the same ids they will have in the database.
+    last_process_log_id: i32,
+    last_compaction_log_id: i32,
+    last_condensation_log_id: i32,
+    last_page_fault_log_id: i32,
+    last_swap_log_id: i32,
+}
+
+impl Simulator {
+    pub fn new(settings: &SimulatorSettings, mut processes: Vec<Process>) -> Simulator {
+        // Processes that were never saved get an id after the biggest one, so
+        // they can still be told apart in the logs.
+        let mut last_id = processes
+            .iter()
+            .filter_map(|process| process.id)
+            .max()
+            .unwrap_or(0);
+        for process in processes.iter_mut() {
+            if process.id.is_none() {
+                last_id += 1;
+                process.id = Some(last_id);
+            }
+        }
+        processes.sort_by_key(|process| process.id);
+
+        let allocation_mode = get_allocation_from_enum(settings.allocation_mode);
+        let memory_size = match allocation_mode {
+            AllocationEnum::DynamicPartitions
+            | AllocationEnum::FixedPartitions
+            | AllocationEnum::Paging
+            | AllocationEnum::Segmentation
+            | AllocationEnum::VirtualMemory => settings.memory_size,
+            // The buddy system only works with a memory size that is a power
+            // of two, so the memory that is left over is not used.
+            AllocationEnum::BuddySystem => buddy::get_buddy_memory_size(settings.memory_size),
+        };
+
+        Simulator {
+            memory: Memory::new(memory_size),
+            processes,
+            iteration: 0,
+            compactions: 0,
+            condensations: 0,
+            partition_consecutive_number: 1,
+            next_fit_position: 0,
+            has_finished: false,
+            placement_strategy: get_placement_strategy_from_enum(settings.placement_strategy),
+            allocation_mode,
+            fixed_partition_sizes: settings.fixed_partition_sizes.clone(),
+            page_size: settings.page_size,
+            replacement_policy: get_replacement_policy_from_enum(settings.replacement_policy),
+            frames_per_process: settings.frames_per_process,
+            reference_positions: HashMap::new(),
+            page_faults: HashMap::new(),
+            swapping: settings.swapping,
+            swap_time: settings.swap_time,
+            swap_times: HashMap::new(),
+            scheduler: get_scheduler_from_enum(settings.scheduling_policy, settings.quantum),
+            cpus: settings.cpus,
+            ready_queue: Vec::new(),
+            quantum_used: HashMap::new(),
+            aging: settings.aging,
+            waiting_times: HashMap::new(),
+            admission_iterations: HashMap::new(),
+            first_run_iterations: HashMap::new(),
+            total_waiting_times: HashMap::new(),
+            io_devices: settings.io_devices,
+            io_queue: Vec::new(),
+            io_times: HashMap::new(),
+            compaction_policy: get_compaction_from_enum(settings.compaction_policy),
+            compaction_threshold: settings.compaction_threshold,
+            log: SimulationLog::default(),
+            events: Vec::new(),
+            last_process_log_id: 0,
+            last_compaction_log_id: 0,
+            last_condensation_log_id: 0,
+            last_page_fault_log_id: 0,
+            last_swap_log_id: 0,
+        }
+    }
+
+    pub fn run(&mut self) {
+        while !self.step() {}
+    }
+
+    /// Runs a single iteration of the processor, returns true when the
+    /// processor has finished.
+    pub fn step(&mut self) -> bool {
+        if self.has_finished {
+            return true;
+        }
+        self.create_iteration_log();
+        self.run_iteration();
+
+        self.events
+            .push(SimulatorEvent::IterationComplete(IterationComplete {
+                iteration: self.iteration,
+                has_finished: self.has_finished,
+                storage_partitions: self.get_storage_partitions(),
+                process_partitions: self.get_process_partitions(),
+                page_tables: self.get_page_tables(),
+                segment_tables: self.get_segment_tables(),
+            }));
+        self.has_finished
+    }
+
+    fn run_iteration(&mut self) {
+        if self.iteration == 1 {
+            // The buddy system starts with the whole memory as a single
+            // partition, that is split as the processes enter.
+            match self.allocation_mode {
+                AllocationEnum::BuddySystem => {
+                    self.create_partition(self.memory.size);
+                }
+                // The partitions are created from the table before any process
+                // enters, and they stay the same until the processor finishes.
+                AllocationEnum::FixedPartitions => self.create_fixed_partitions(),
+                AllocationEnum::Paging | AllocationEnum::VirtualMemory => self.create_frames(),
+                AllocationEnum::DynamicPartitions | AllocationEnum::Segmentation => {}
+            }
+            // This means there is no ready processes in the processor and
+            // none is going to arrive, so it has finished.
+            if !self.add_processes_to_memory() && !self.has_processes_to_arrive() {
+                self.has_finished = true;
+                return;
+            }
+            // Here a new partition is generated with the remaining empty
+            // space, this is only done when the processor starts. A paged
+            // memory has no partitions, only frames.
+            if !self.is_paged() {
+                self.create_partition_from_remaining_space();
+            }
+            // Log the start of the partitions
+            self.create_storage_partition_logs();
+            self.create_frame_logs();
+            self.create_fragmentation_log();
+            return;
+        }
+
+        // The processes that finish their I/O can run in this same iteration.
+        self.advance_io();
+        self.process_resident_processes();
+        self.advance_swaps();
+        self.create_storage_partition_logs();
+        self.create_frame_logs();
+        self.create_fragmentation_log();
+
+        match self.allocation_mode {
+            // Before trying to add a new process to the memory, check if the
+            // partitions can be compacted, so start by swapping process
+            // partitions with empty partitions and then merge all the empty
+            // swapped partitions. Without compacting, only the empty
+            // partitions that are already next to each other are merged.
+            AllocationEnum::DynamicPartitions | AllocationEnum::Segmentation => {
+                if self.should_compact() {
+                    self.swap_process_partitions_with_empty_partitions();
+                }
+                self.merge_partitions();
+            }
+            // The buddies can't be moved, only the empty ones are coalesced.
+            AllocationEnum::BuddySystem => self.merge_buddy_partitions(),
+            // Fixed partitions are never moved nor merged.
+            AllocationEnum::FixedPartitions => {}
+            // The pages don't need to be next to each other, so there is
+            // nothing to compact.
+            AllocationEnum::Paging | AllocationEnum::VirtualMemory => {}
+        }
+
+        // If the processor couldn't add processes after the compaction, the
+        // partitions are empty and no process is going to arrive, the
+        // processor has finished.
+        if !self.add_processes_to_memory()
+            && self.get_resident_processes_count() == 0
+            && !self.has_processes_to_arrive()
+        {
+            self.has_finished = true;
+        }
+    }
+
+    /// Takes out everything logged since the last time it was taken.
+    pub fn take_log(&mut self) -> SimulationLog {
+        std::mem::take(&mut self.log)
+    }
+
+    /// Takes out every event since the last time they were taken.
+    pub fn take_events(&mut self) -> Vec<SimulatorEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// The partitions are numbered consecutively, so the partition number is
+    /// used as its id.
+    pub fn get_storage_partitions(&self) -> Vec<models::StoragePartition> {
+        let mut storage_partitions = Vec::new();
+        for i in 0..self.memory.partitions.len() {
+            let partition = &self.memory.partitions[i];
+            let position_start = self.memory.get_position_start(i);
+            storage_partitions.push(models::StoragePartition {
+                id: partition.number,
+                number: partition.number,
+                position: i as i32,
+                position_start,
+                position_end: position_start + partition.size,
+                size: partition.size,
+            });
+        }
+        storage_partitions
+    }
+
+    pub fn get_process_partitions(&self) -> Vec<models::ProcessPartition> {
+        self.memory
+            .partitions
+            .iter()
+            .filter_map(|partition| {
+                partition
+                    .process_id
+                    .map(|process_id| models::ProcessPartition {
+                        process_id,
+                        storage_partition_id: partition.number,
+                    })
+            })
+            .collect()
+    }
+
+    /// The time left and the state of every process, the processes given to
+    /// the simulator are never changed in the database.
+    pub fn get_process_states(&self) -> Vec<models::ProcessState> {
+        self.processes
+            .iter()
+            .map(|process| models::ProcessState {
+                process_id: process.id.unwrap(),
+                time: process.time,
+                state: process.state.as_ref().unwrap().get_state_number(),
+                cpu_time: process.cpu_time,
+            })
+            .collect()
+    }
+
+    pub fn get_resident_processes_count(&self) -> usize {
+        self.processes
+            .iter()
+            .filter(|process| {
+                self.memory
+                    .find_storage_with_process_id(process.id.unwrap())
+                    .is_some()
+            })
+            .count()
+    }
+
+    fn create_iteration_log(&mut self) {
+        self.iteration += 1;
+        self.log
+            .iterations
+            .push(models::IterationLog { id: self.iteration });
+    }
+
+    fn create_storage_partition_logs(&mut self) {
+        for i in 0..self.memory.partitions.len() {
+            let internal_fragmentation = self.get_internal_fragmentation(i);
+            self.log
+                .storage_partition_logs
+                .push(models::NewStoragePartitionLog {
+                    iteration: self.iteration,
+                    storage_partition_id: self.memory.partitions[i].number,
+                    position: i as i32,
+                    size: self.memory.partitions[i].size,
+                    internal_fragmentation,
+                });
+        }
+    }
+
+    /// The space of the partition that is not used by the process in it, an
+    /// empty partition has no internal fragmentation.
+    fn get_internal_fragmentation(&self, position: usize) -> i32 {
+        let partition = &self.memory.partitions[position];
+        partition
+            .process_id
+            .and_then(|process_id| {
+                self.processes
+                    .iter()
+                    .find(|process| process.id == Some(process_id))
+            })
+            .map(|process| match partition.segment {
+                Some(segment) => partition.size - process.get_segments()[segment as usize].size,
+                None => partition.size - process.size,
+            })
+            .unwrap_or(0)
+    }
+
+    fn process_resident_processes(&mut self) {
+        // Only the processes that got a CPU run, the others wait in their
+        // partitions.
+        let scheduled_processes = self.schedule_processes();
+        let mut running_processes = Vec::new();
+        let mut blocked_processes = Vec::new();
+        let mut finished_processes = Vec::new();
+        for process in self.processes.iter_mut() {
+            let process_id = process.id.unwrap();
+            let storage_id = match self.memory.find_storage_with_process_id(process_id) {
+                Some(storage_id) => storage_id,
+                None => continue,
+            };
+
+            let cpu_time = process.cpu_time;
+            if scheduled_processes.contains(&process_id) {
+                self.first_run_iterations
+                    .entry(process_id)
+                    .or_insert(self.iteration);
+                // Without a limit of CPUs the processes leave their CPU after
+                // every iteration they run, as they always did.
+                if self.cpus == 0 {
+                    process.process();
+                } else {
+                    process.run();
+                }
+            } else {
+                process.preempt();
+            }
+
+            let state = process.state.as_ref().unwrap().get_state_number();
+            let has_finished = state == StateEnum::Finished as i32;
+            // A process that is blocked or has finished after using its CPU
+            // has still run in this iteration.
+            let has_run = process.cpu_time > cpu_time;
+            let has_blocked = state == StateEnum::Blocked as i32 && has_run;
+            if has_run {
+                running_processes.push(process_id);
+            }
+            if has_blocked {
+                blocked_processes.push(process_id);
+            }
+            let mut storage_partition_id = storage_id;
+            if has_finished {
+                // Add the finished process to the finished process list with
+                // the information of the partition the process was in, and
+                // take it out of the partition.
+                self.log
+                    .finished_processes
+                    .push(models::NewFinishedProcess {
+                        process_id,
+                        partition_number: storage_id,
+                        admission_iteration: self.admission_iterations[&process_id],
+                        first_run_iteration: self.first_run_iterations[&process_id],
+                        finish_iteration: self.iteration,
+                        waiting_time: *self.total_waiting_times.get(&process_id).unwrap_or(&0),
+                    });
+                finished_processes.push(process_id);
+                storage_partition_id = -1;
+            }
+
+            self.last_process_log_id += 1;
+            let process_log = models::ProcessLog {
+                id: self.last_process_log_id,
+                iteration: self.iteration,
+                process_id,
+                storage_partition_id,
+                time_remaining: process.time,
+                state,
+            };
+            if has_finished {
+                self.events
+                    .push(SimulatorEvent::ProcessFinished(process_log.clone()));
+            }
+            self.log.process_logs.push(process_log);
+        }
+
+        for process_id in blocked_processes {
+            self.block_process(process_id);
+        }
+
+        // With virtual memory every process that ran references a page, which
+        // is loaded if it is not in memory.
+        if self.allocation_mode == AllocationEnum::VirtualMemory {
+            for process_id in running_processes {
+                self.reference_next_page(process_id);
+            }
+        }
+
+        // The finished processes leave the memory once they have referenced
+        // their last page.
+        for process_id in finished_processes {
+            self.memory.free_process(process_id);
+        }
+    }
+
+    fn add_processes_to_memory(&mut self) -> bool {
+        // The swapped processes wait to enter the memory again the same way
+        // the ready ones do, the processes that have not arrived yet are not
+        // taken into account.
+        let mut ready_processes: Vec<(i32, i32, bool, i32)> = self
+            .processes
+            .iter()
+            .filter_map(|process| {
+                let state = process.state.as_ref().unwrap().get_state_number();
+                if (state == StateEnum::Ready as i32 && process.arrival <= self.iteration)
+                    || state == StateEnum::Swapped as i32
+                {
+                    Some((
+                        process.id.unwrap(),
+                        process.size,
+                        state == StateEnum::Swapped as i32,
+                        self.get_effective_priority(process),
+                    ))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        // The processes that have never entered go first, so the memory freed
+        // for them is not taken by the swapped processes, then the ones with
+        // the highest priority.
+        ready_processes.sort_by_key(|&(_, _, is_swapped, priority)| (is_swapped, priority));
+
+        // If there is no ready process in the main list, it means the processor
+        // has finished.
+        if ready_processes.is_empty() {
+            return false;
+        }
+
+        // If it finishes without adding any process to memory, it means the
+        // memory is full or the partitions are not big enough.
+        let mut has_added_processes_to_memory = false;
+        for (process_id, process_size, is_swapped, _) in ready_processes {
+            // If the process is ready but is already on a partition, do not add
+            if self
+                .memory
+                .find_storage_with_process_id(process_id)
+                .is_some()
+            {
+                continue;
+            }
+            // First, try to get an empty partition to enter, if there is none,
+            // try to create one, if it can't then the process has to wait for
+            // the processes to end and for a merge to occur.
+            let storage_partition_id = match self.allocation_mode {
+                AllocationEnum::DynamicPartitions => {
+                    let position = self.get_dynamic_partition(process_size);
+                    self.place_process_in_partition(position, process_id)
+                }
+                AllocationEnum::BuddySystem => {
+                    let position = self.get_buddy_partition(process_size);
+                    self.place_process_in_partition(position, process_id)
+                }
+                AllocationEnum::FixedPartitions => {
+                    let position = self.get_fixed_partition(process_size);
+                    self.place_process_in_partition(position, process_id)
+                }
+                AllocationEnum::Paging => self.allocate_frames(process_id, process_size),
+                AllocationEnum::Segmentation => {
+                    // The segments that were placed are taken back when one
+                    // doesn't fit, so they can be placed again after compacting.
+                    self.allocate_segments(process_id).or_else(|| {
+                        if self.compact_on_failure(process_size) {
+                            self.allocate_segments(process_id)
+                        } else {
+                            None
+                        }
+                    })
+                }
+                AllocationEnum::VirtualMemory => self.reserve_frames(process_id, process_size),
+            };
+
+            if let Some(storage_partition_id) = storage_partition_id {
+                self.events
+                    .push(SimulatorEvent::ProcessAdmitted(models::ProcessPartition {
+                        process_id,
+                        storage_partition_id,
+                    }));
+                if is_swapped {
+                    self.start_swap(process_id, StateEnum::SwappingIn);
+                }
+                self.admission_iterations
+                    .entry(process_id)
+                    .or_insert(self.iteration);
+                has_added_processes_to_memory = true;
+            } else {
+                self.add_waiting_time(process_id);
+                if self.swapping && !is_swapped {
+                    // Only the processes that have never entered make others
+                    // leave, so the swapped processes don't keep swapping each
+                    // other out.
+                    self.swap_out_for(process_size);
+                }
+            }
+        }
+        has_added_processes_to_memory
+    }
+
+    /// Puts the process in the partition, if there is one, and returns the
+    /// partition number.
+    fn place_process_in_partition(
+        &mut self,
+        position: Option<usize>,
+        process_id: i32,
+    ) -> Option<i32> {
+        let partition = &mut self.memory.partitions[position?];
+        partition.process_id = Some(process_id);
+        Some(partition.number)
+    }
+
+    fn get_empty_partition(&mut self, process_size: i32) -> Option<usize> {
+        let empty_partitions = self.memory.get_empty_partitions();
+        let selected_partition = self.placement_strategy.select_partition(
+            &empty_partitions,
+            process_size,
+            self.next_fit_position,
+        )?;
+        let position = empty_partitions[selected_partition].position as usize;
+        // Next-fit keeps searching from the partition after this one, be it the
+        // next partition or the remaining space of the split.
+        self.next_fit_position = position as i32 + 1;
+
+        // If the partition is bigger than the process, it is split into a
+        // partition only for the process and one with the remaining space.
+        let partition_size = self.memory.partitions[position].size;
+        if partition_size > process_size {
+            let process_partition = Partition::new(self.next_partition_number(), process_size);
+            let remaining_partition =
+                Partition::new(self.next_partition_number(), partition_size - process_size);
+            self.events
+                .push(SimulatorEvent::PartitionSplit(PartitionSplit {
+                    iteration: self.iteration,
+                    partition: self.memory.partitions[position].number,
+                    partition_size,
+                    process_partition: process_partition.number,
+                    process_partition_size: process_partition.size,
+                    remaining_partition: remaining_partition.number,
+                    remaining_partition_size: remaining_partition.size,
+                }));
+            self.memory.partitions[position] = process_partition;
+            self.memory
+                .partitions
+                .insert(position + 1, remaining_partition);
+        }
+        Some(position)
+    }
+
+    /// Gets an empty partition for the process, or creates one in the space
+    /// that is left. With the on failure policy the memory is compacted to
+    /// make room for the process if there is none.
+    fn get_dynamic_partition(&mut self, process_size: i32) -> Option<usize> {
+        let position = self
+            .get_empty_partition(process_size)
+            .or_else(|| self.create_partition(process_size));
+        if position.is_some() || !self.compact_on_failure(process_size) {
+            return position;
+        }
+        self.get_empty_partition(process_size)
+    }
+
+    fn create_partition(&mut self, size: i32) -> Option<usize> {
+        if size > self.memory.get_remaining_space() {
+            return None;
+        }
+        let partition = Partition::new(self.next_partition_number(), size);
+        self.memory.partitions.push(partition);
+        Some(self.memory.partitions.len() - 1)
+    }
+
+    fn create_partition_from_remaining_space(&mut self) {
+        let remaining_space = self.memory.get_remaining_space();
+        if remaining_space > 0 {
+            self.create_partition(remaining_space);
+        }
+    }
+
+    fn should_compact(&self) -> bool {
+        match self.compaction_policy {
+            CompactionEnum::Always => true,
+            CompactionEnum::Threshold => {
+                self.get_external_fragmentation() * 100.0 >= self.compaction_threshold as f64
+            }
+            CompactionEnum::OnFailure | CompactionEnum::Never => false,
+        }
+    }
+
+    /// Compacts the memory for a process that didn't fit if the policy is on
+    /// failure and the process fits in all the free memory together, returns
+    /// if it did.
+    fn compact_on_failure(&mut self, process_size: i32) -> bool {
+        let free_memory: i32 = self
+            .memory
+            .get_empty_partitions()
+            .iter()
+            .map(|partition| partition.size)
+            .sum::<i32>()
+            + self.memory.get_remaining_space();
+        if self.compaction_policy != CompactionEnum::OnFailure || free_memory < process_size {
+            return false;
+        }
+        self.swap_process_partitions_with_empty_partitions();
+        self.merge_partitions();
+        true
+    }
+
+    fn swap_process_partitions_with_empty_partitions(&mut self) {
+        let mut made_compaction = false;
+        for i in 0..self.memory.partitions.len() {
+            // If the partition is empty, search the next non-empty partition
+            // and swap them, so the empty partitions are always at the end.
+            if self.memory.partitions[i].process_id.is_some() {
+                continue;
+            }
+            let next_process_partition = (i..self.memory.partitions.len())
+                .find(|&e| self.memory.partitions[e].process_id.is_some());
+
+            if let Some(e) = next_process_partition {
+                let previous_position = self.memory.get_position_start(e);
+                let final_position = self.memory.get_position_start(i);
+                self.memory.partitions.swap(i, e);
+                // Save log of the part of the compaction done
+                self.last_compaction_log_id += 1;
+                let compaction_log = models::CompactionLog {
+                    id: self.last_compaction_log_id,
+                    iteration: self.iteration,
+                    partition: self.memory.partitions[i].number,
+                    previous_position,
+                    final_position,
+                };
+                self.events
+                    .push(SimulatorEvent::CompactionSwap(compaction_log.clone()));
+                self.log.compaction_logs.push(compaction_log);
+                made_compaction = true;
+            }
+        }
+
+        // If made at least 1 swap, it means it did a compaction.
+        if made_compaction {
+            self.compactions += 1;
+        }
+    }
+
+    fn merge_partitions(&mut self) {
+        let mut i = 0;
+        while i < self.memory.partitions.len() {
+            if self.memory.partitions[i].process_id.is_none() {
+                // Start from the empty partition and see if the next
+                // partitions are empty
+                let end = (i..self.memory.partitions.len())
+                    .find(|&e| self.memory.partitions[e].process_id.is_some())
+                    .unwrap_or(self.memory.partitions.len());
+
+                // More than 1 adjacent empty partitions are merged into a new
+                // one, as if the partition was big enough from the start.
+                if end - i > 1 {
+                    let merged_partitions: Vec<Partition> =
+                        self.memory.partitions.drain(i..end).collect();
+                    let mut new_partition_size = 0;
+                    merged_partitions
+                        .iter()
+                        .for_each(|partition| new_partition_size += partition.size);
+                    let new_partition =
+                        Partition::new(self.next_partition_number(), new_partition_size);
+
+                    for partition in &merged_partitions {
+                        self.create_condensation_log(partition, &new_partition);
+                    }
+                    self.memory.partitions.insert(i, new_partition);
+                    self.condensations += 1;
+                }
+            }
+            i += 1;
+        }
+    }
+
+    fn create_condensation_log(&mut self, partition: &Partition, new_partition: &Partition) {
+        self.last_condensation_log_id += 1;
+        let condensation_log = models::CondensationLog {
+            id: self.last_condensation_log_id,
+            partition: partition.number,
+            partition_size: partition.size,
+            new_partition: new_partition.number,
+            new_partition_size: new_partition.size,
+        };
+        self.events
+            .push(SimulatorEvent::Condensation(condensation_log.clone()));
+        self.log.condensation_logs.push(condensation_log);
+    }
+
+    /// The process waited an iteration to enter the memory or to get a CPU.
+    fn add_waiting_time(&mut self, process_id: i32) {
+        *self.waiting_times.entry(process_id).or_insert(0) += 1;
+        *self.total_waiting_times.entry(process_id).or_insert(0) += 1;
+    }
+
+    /// The priority of the process after aging, it goes up by one every time
+    /// the process waits the aging iterations.
+    fn get_effective_priority(&self, process: &Process) -> i32 {
+        if self.aging <= 0 {
+            return process.priority;
+        }
+        let waiting_time = *self.waiting_times.get(&process.id.unwrap()).unwrap_or(&0);
+        process.priority - waiting_time / self.aging
+    }
+
+    /// If there are processes that arrive after this iteration.
+    fn has_processes_to_arrive(&self) -> bool {
+        self.processes
+            .iter()
+            .any(|process| process.arrival > self.iteration)
+    }
+
+    fn is_paged(&self) -> bool {
+        self.allocation_mode == AllocationEnum::Paging
+            || self.allocation_mode == AllocationEnum::VirtualMemory
+    }
+
+    fn next_partition_number(&mut self) -> i32 {
+        let number = self.partition_consecutive_number;
+        self.partition_consecutive_number += 1;
+        number
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_states(simulator: &mut Simulator) -> Vec<(i32, i32, i32)> {
+        simulator
+            .take_log()
+            .process_logs
+            .iter()
+            .map(|process_log| {
+                (
+                    process_log.process_id,
+                    process_log.time_remaining,
+                    process_log.state,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn every_resident_process_runs_by_default() {
+        let processes = vec![
+            Process::new("P1".to_string(), 2, 10),
+            Process::new("P2".to_string(), 1, 10),
+        ];
+        let mut simulator = Simulator::new(&SimulatorSettings::default(), processes);
+        simulator.step();
+        simulator.take_log();
+
+        let running = StateEnum::Running as i32;
+        let ready_in_partition = StateEnum::ReadyInPartition as i32;
+        let finished = StateEnum::Finished as i32;
+        simulator.step();
+        assert_eq!(
+            get_states(&mut simulator),
+            vec![(1, 1, running), (2, 0, running)]
+        );
+        simulator.step();
+        assert_eq!(
+            get_states(&mut simulator),
+            vec![(1, 1, ready_in_partition), (2, 0, finished)]
+        );
+        simulator.step();
+        assert_eq!(get_states(&mut simulator), vec![(1, 0, running)]);
+        simulator.step();
+        assert_eq!(get_states(&mut simulator), vec![(1, 0, finished)]);
+        assert!(simulator.step());
+    }
+
+    #[test]
+    fn only_the_scheduled_processes_run_with_a_number_of_cpus() {
+        let settings = SimulatorSettings {
+            cpus: 1,
+            scheduling_policy: SchedulingEnum::Fcfs as i32,
+            ..SimulatorSettings::default()
+        };
+        let processes = vec![
+            Process::new("P1".to_string(), 2, 10),
+            Process::new("P2".to_string(), 1, 10),
+        ];
+        let mut simulator = Simulator::new(&settings, processes);
+        simulator.step();
+        simulator.take_log();
+
+        let running = StateEnum::Running as i32;
+        let ready = StateEnum::Ready as i32;
+        let finished = StateEnum::Finished as i32;
+        simulator.step();
+        assert_eq!(
+            get_states(&mut simulator),
+            vec![(1, 1, running), (2, 1, ready)]
+        );
+        simulator.step();
+        assert_eq!(
+            get_states(&mut simulator),
+            vec![(1, 0, finished), (2, 1, ready)]
+        );
+        simulator.step();
+        assert_eq!(get_states(&mut simulator), vec![(2, 0, finished)]);
+    }
+
+    #[test]
+    fn the_processes_have_to_fit_in_the_space_of_the_allocation_mode() {
+        let check_size = |allocation_mode: AllocationEnum, size: i32| {
+            let settings = SimulatorSettings {
+                allocation_mode: allocation_mode as i32,
+                fixed_partition_sizes: vec![10, 15],
+                ..SimulatorSettings::default()
+            };
+            settings
+                .check_processes(&[Process::new("P1".to_string(), 1, size)])
+                .is_ok()
+        };
+        assert!(check_size(AllocationEnum::DynamicPartitions, 50));
+        assert!(!check_size(AllocationEnum::DynamicPartitions, 51));
+        assert!(check_size(AllocationEnum::FixedPartitions, 25));
+        assert!(!check_size(AllocationEnum::FixedPartitions, 26));
+        assert!(check_size(AllocationEnum::BuddySystem, 32));
+        assert!(!check_size(AllocationEnum::BuddySystem, 33));
+        assert!(check_size(AllocationEnum::Paging, 48));
+        assert!(!check_size(AllocationEnum::Paging, 49));
+        assert!(check_size(AllocationEnum::VirtualMemory, 100));
+    }
+
+    #[test]
+    fn the_frames_reserved_with_virtual_memory_have_to_be_in_memory() {
+        let check_frames = |page_size: i32, frames_per_process: i32| {
+            let settings = SimulatorSettings {
+                allocation_mode: AllocationEnum::VirtualMemory as i32,
+                page_size,
+                frames_per_process,
+                ..SimulatorSettings::default()
+            };
+            settings
+                .check_processes(&[Process::new("P1".to_string(), 1, 100)])
+                .is_ok()
+        };
+        assert!(check_frames(4, 3));
+        assert!(check_frames(4, 12));
+        assert!(!check_frames(4, 13));
+        assert!(!check_frames(60, 1));
+    }
+
+    #[test]
+    fn the_settings_out_of_range_are_not_valid() {
+        assert!(SimulatorSettings::default().validate().is_ok());
+        let settings = SimulatorSettings {
+            placement_strategy: PlacementEnum::NextFit as i32 + 1,
+            ..SimulatorSettings::default()
+        };
+        assert!(settings.validate().is_err());
+        let settings = SimulatorSettings {
+            fixed_partition_sizes: vec![30, 30],
+            ..SimulatorSettings::default()
+        };
+        assert!(settings.validate().is_err());
+        let settings = SimulatorSettings {
+            cpus: -1,
+            ..SimulatorSettings::default()
+        };
+        assert!(settings.validate().is_err());
+    }
+}
@@ -0,0 +1,160 @@
+use crate::model::event::{PartitionSplit, SimulatorEvent};
+use crate::model::memory::Partition;
+
+use super::Simulator;
+
+impl Simulator {
+    /// Gets the smallest empty partition the process fits in, and splits it in
+    /// halves until it is the smallest power of two the process fits in.
+    pub(super) fn get_buddy_partition(&mut self, process_size: i32) -> Option<usize> {
+        let buddy_size = get_buddy_size(process_size);
+
+        let mut selected_partition: Option<usize> = None;
+        for i in 0..self.memory.partitions.len() {
+            let partition = &self.memory.partitions[i];
+            if partition.process_id.is_none()
+                && partition.size >= buddy_size
+                && (selected_partition.is_none()
+                    || partition.size < self.memory.partitions[selected_partition.unwrap()].size)
+            {
+                selected_partition = Some(i);
+            }
+        }
+
+        let position = selected_partition?;
+        while self.memory.partitions[position].size > buddy_size {
+            self.split_buddy_partition(position);
+        }
+        Some(position)
+    }
+
+    /// Coalesces the empty buddies until there are no more empty buddies next
+    /// to each other.
+    pub(super) fn merge_buddy_partitions(&mut self) {
+        loop {
+            let mut has_finished_merging = true;
+            for i in 0..self.memory.partitions.len().saturating_sub(1) {
+                let left_partition = &self.memory.partitions[i];
+                let right_partition = &self.memory.partitions[i + 1];
+                let size = left_partition.size;
+                // Two partitions are buddies if they have the same size and the
+                // left one is where a partition twice as big would start.
+                let are_empty_buddies = size == right_partition.size
+                    && self.memory.get_position_start(i) % (size * 2) == 0
+                    && left_partition.process_id.is_none()
+                    && right_partition.process_id.is_none();
+
+                if are_empty_buddies {
+                    let new_partition = Partition::new(self.next_partition_number(), size * 2);
+                    let buddies: Vec<Partition> = self.memory.partitions.drain(i..i + 2).collect();
+                    for buddy in &buddies {
+                        self.create_condensation_log(buddy, &new_partition);
+                    }
+                    self.memory.partitions.insert(i, new_partition);
+                    self.condensations += 1;
+                    has_finished_merging = false;
+                    break;
+                }
+            }
+
+            if has_finished_merging {
+                break;
+            }
+        }
+    }
+
+    /// Splits the partition in two buddies, the split is logged as a
+    /// condensation from the partition to each one of its halves.
+    fn split_buddy_partition(&mut self, position: usize) {
+        let partition = Partition::new(
+            self.memory.partitions[position].number,
+            self.memory.partitions[position].size,
+        );
+        let left_partition = Partition::new(self.next_partition_number(), partition.size / 2);
+        let right_partition = Partition::new(self.next_partition_number(), partition.size / 2);
+
+        self.create_condensation_log(&partition, &left_partition);
+        self.create_condensation_log(&partition, &right_partition);
+        self.events
+            .push(SimulatorEvent::PartitionSplit(PartitionSplit {
+                iteration: self.iteration,
+                partition: partition.number,
+                partition_size: partition.size,
+                process_partition: left_partition.number,
+                process_partition_size: left_partition.size,
+                remaining_partition: right_partition.number,
+                remaining_partition_size: right_partition.size,
+            }));
+
+        self.memory.partitions[position] = left_partition;
+        self.memory.partitions.insert(position + 1, right_partition);
+    }
+}
+
+/// The smallest power of two the size fits in.
+pub fn get_buddy_size(size: i32) -> i32 {
+    let mut buddy_size = 1;
+    while buddy_size < size {
+        buddy_size *= 2;
+    }
+    buddy_size
+}
+
+/// The biggest power of two that fits in the memory.
+pub fn get_buddy_memory_size(memory_size: i32) -> i32 {
+    let mut buddy_memory_size = 1;
+    while buddy_memory_size * 2 <= memory_size {
+        buddy_memory_size *= 2;
+    }
+    buddy_memory_size
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::allocation::AllocationEnum;
+    use crate::model::process::Process;
+    use crate::model::simulator::SimulatorSettings;
+
+    fn get_partition_sizes(simulator: &Simulator) -> Vec<(i32, Option<i32>)> {
+        simulator
+            .memory
+            .partitions
+            .iter()
+            .map(|partition| (partition.size, partition.process_id))
+            .collect()
+    }
+
+    #[test]
+    fn the_sizes_are_powers_of_two() {
+        assert_eq!(get_buddy_size(1), 1);
+        assert_eq!(get_buddy_size(10), 16);
+        assert_eq!(get_buddy_size(16), 16);
+        assert_eq!(get_buddy_memory_size(50), 32);
+        assert_eq!(get_buddy_memory_size(64), 64);
+    }
+
+    #[test]
+    fn the_memory_is_split_for_the_processes_and_merged_when_they_leave() {
+        let settings = SimulatorSettings {
+            memory_size: 64,
+            allocation_mode: AllocationEnum::BuddySystem as i32,
+            ..SimulatorSettings::default()
+        };
+        let processes = vec![
+            Process::new("P1".to_string(), 1, 10),
+            Process::new("P2".to_string(), 3, 20),
+        ];
+        let mut simulator = Simulator::new(&settings, processes);
+
+        simulator.step();
+        assert_eq!(
+            get_partition_sizes(&simulator),
+            vec![(16, Some(1)), (16, None), (32, Some(2))]
+        );
+
+        simulator.run();
+        assert_eq!(get_partition_sizes(&simulator), vec![(64, None)]);
+        assert!(simulator.condensations > 0);
+    }
+}
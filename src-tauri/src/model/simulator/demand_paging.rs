@@ -0,0 +1,184 @@
+use crate::database::models;
+use crate::model::event::SimulatorEvent;
+use crate::model::replacement::ResidentPage;
+
+use super::paging::get_page_count;
+use super::Simulator;
+
+impl Simulator {
+    /// Reserves the frames of the process without loading any of its pages,
+    /// they are loaded when the process references them. A process gets a frame
+    /// for every page, up to the frames per process.
+    pub(super) fn reserve_frames(&mut self, process_id: i32, process_size: i32) -> Option<i32> {
+        let frame_count = get_page_count(process_size, self.page_size)
+            .min(self.frames_per_process)
+            .max(1) as usize;
+        let free_frames: Vec<usize> = (0..self.memory.frames.len())
+            .filter(|&i| self.memory.frames[i].process_id.is_none())
+            .take(frame_count)
+            .collect();
+        if free_frames.len() < frame_count {
+            return None;
+        }
+
+        for &i in &free_frames {
+            self.memory.frames[i].process_id = Some(process_id);
+        }
+        Some(self.memory.frames[free_frames[0]].number)
+    }
+
+    /// The process references the next page of its reference string, if the
+    /// page is not in memory there is a page fault and the page is loaded in
+    /// one of the frames of the process.
+    pub(super) fn reference_next_page(&mut self, process_id: i32) {
+        let process = self
+            .processes
+            .iter()
+            .find(|process| process.id == Some(process_id))
+            .unwrap();
+        let reference_string = if process.reference_string.is_empty() {
+            // A process without a reference string goes through its pages in
+            // order.
+            (0..get_page_count(process.size, self.page_size)).collect()
+        } else {
+            process.reference_string.clone()
+        };
+        let position = *self.reference_positions.get(&process_id).unwrap_or(&0);
+        let page = reference_string[position % reference_string.len()];
+        let future_references: Vec<i32> = (1..=process.time as usize)
+            .map(|i| reference_string[(position + i) % reference_string.len()])
+            .collect();
+        self.reference_positions.insert(process_id, position + 1);
+
+        if let Some(frame) = self
+            .memory
+            .frames
+            .iter_mut()
+            .find(|frame| frame.process_id == Some(process_id) && frame.page == Some(page))
+        {
+            frame.last_used = self.iteration;
+            frame.referenced = true;
+            return;
+        }
+
+        // The page is loaded in an empty frame of the process, if all of them
+        // are used a page has to leave.
+        let position = self
+            .memory
+            .frames
+            .iter()
+            .position(|frame| frame.process_id == Some(process_id) && frame.page.is_none())
+            .unwrap_or_else(|| self.select_victim_frame(process_id, &future_references));
+        let frame = &mut self.memory.frames[position];
+        let victim_page = frame.page;
+        frame.page = Some(page);
+        frame.loaded_at = self.iteration;
+        frame.last_used = self.iteration;
+        frame.referenced = true;
+        let frame_number = frame.number;
+
+        let faults = self.page_faults.entry(process_id).or_insert(0);
+        *faults += 1;
+        self.last_page_fault_log_id += 1;
+        let page_fault_log = models::PageFaultLog {
+            id: self.last_page_fault_log_id,
+            iteration: self.iteration,
+            process_id,
+            page,
+            frame: frame_number,
+            victim_page,
+            faults: *faults,
+        };
+        self.events
+            .push(SimulatorEvent::PageFault(page_fault_log.clone()));
+        self.log.page_fault_logs.push(page_fault_log);
+    }
+
+    /// Uses the replacement policy to choose which frame of the process is
+    /// emptied for the page that is going to be loaded.
+    fn select_victim_frame(&mut self, process_id: i32, future_references: &[i32]) -> usize {
+        let positions: Vec<usize> = (0..self.memory.frames.len())
+            .filter(|&i| self.memory.frames[i].process_id == Some(process_id))
+            .collect();
+        let mut pages: Vec<ResidentPage> = positions
+            .iter()
+            .map(|&i| {
+                let frame = &self.memory.frames[i];
+                ResidentPage {
+                    frame: frame.number,
+                    page: frame.page.unwrap(),
+                    loaded_at: frame.loaded_at,
+                    last_used: frame.last_used,
+                    referenced: frame.referenced,
+                }
+            })
+            .collect();
+
+        let victim = self
+            .replacement_policy
+            .select_victim(&mut pages, future_references);
+        // Clock clears the reference bit of the pages it gives a second chance.
+        for i in 0..positions.len() {
+            self.memory.frames[positions[i]].referenced = pages[i].referenced;
+        }
+        positions[victim]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::allocation::AllocationEnum;
+    use crate::model::process::Process;
+    use crate::model::replacement::ReplacementEnum;
+    use crate::model::simulator::SimulatorSettings;
+
+    const REFERENCE_STRING: [i32; 20] =
+        [7, 0, 1, 2, 0, 3, 0, 4, 2, 3, 0, 3, 2, 1, 2, 0, 1, 7, 0, 1];
+
+    fn count_page_faults(replacement_policy: ReplacementEnum, cpus: i32) -> usize {
+        let settings = SimulatorSettings {
+            allocation_mode: AllocationEnum::VirtualMemory as i32,
+            page_size: 1,
+            replacement_policy: replacement_policy as i32,
+            frames_per_process: 3,
+            cpus,
+            ..SimulatorSettings::default()
+        };
+        let mut process = Process::new("P1".to_string(), REFERENCE_STRING.len() as i32, 8);
+        process
+            .set_reference_string(REFERENCE_STRING.to_vec(), settings.page_size)
+            .unwrap();
+        let mut simulator = Simulator::new(&settings, vec![process]);
+        simulator.run();
+        simulator.take_log().page_fault_logs.len()
+    }
+
+    #[test]
+    fn fifo_replaces_the_oldest_page() {
+        assert_eq!(count_page_faults(ReplacementEnum::Fifo, 0), 15);
+    }
+
+    #[test]
+    fn lru_replaces_the_least_recently_used_page() {
+        assert_eq!(count_page_faults(ReplacementEnum::Lru, 0), 12);
+    }
+
+    #[test]
+    fn clock_gives_the_referenced_pages_a_second_chance() {
+        assert_eq!(count_page_faults(ReplacementEnum::Clock, 0), 14);
+    }
+
+    #[test]
+    fn optimal_replaces_the_page_used_the_latest() {
+        assert_eq!(count_page_faults(ReplacementEnum::Optimal, 0), 9);
+    }
+
+    #[test]
+    fn the_last_page_is_referenced_with_a_scheduler() {
+        assert_eq!(count_page_faults(ReplacementEnum::Fifo, 1), 15);
+        assert_eq!(count_page_faults(ReplacementEnum::Lru, 1), 12);
+        assert_eq!(count_page_faults(ReplacementEnum::Clock, 1), 14);
+        assert_eq!(count_page_faults(ReplacementEnum::Optimal, 1), 9);
+    }
+}
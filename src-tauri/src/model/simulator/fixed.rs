@@ -0,0 +1,82 @@
+use super::Simulator;
+
+impl Simulator {
+    /// Creates the partitions of the table in order, the ones that don't fit
+    /// in the memory are left out. The space left after them becomes one more
+    /// partition, so if there is no table the whole memory is a single one.
+    pub(super) fn create_fixed_partitions(&mut self) {
+        for size in self.fixed_partition_sizes.clone() {
+            self.create_partition(size);
+        }
+        self.create_partition_from_remaining_space();
+    }
+
+    /// Gets an empty partition the process fits in with the placement
+    /// strategy, the partition is never split so the space the process
+    /// doesn't use is wasted until it leaves.
+    pub(super) fn get_fixed_partition(&mut self, process_size: i32) -> Option<usize> {
+        let empty_partitions = self.memory.get_empty_partitions();
+        let selected_partition = self.placement_strategy.select_partition(
+            &empty_partitions,
+            process_size,
+            self.next_fit_position,
+        )?;
+        let position = empty_partitions[selected_partition].position as usize;
+        self.next_fit_position = position as i32 + 1;
+        Some(position)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::allocation::AllocationEnum;
+    use crate::model::process::Process;
+    use crate::model::simulator::SimulatorSettings;
+
+    fn create_simulator(fixed_partition_sizes: Vec<i32>, sizes: &[i32]) -> Simulator {
+        let settings = SimulatorSettings {
+            allocation_mode: AllocationEnum::FixedPartitions as i32,
+            fixed_partition_sizes,
+            ..SimulatorSettings::default()
+        };
+        let processes = sizes
+            .iter()
+            .enumerate()
+            .map(|(i, &size)| Process::new(format!("P{}", i + 1), 1, size))
+            .collect();
+        Simulator::new(&settings, processes)
+    }
+
+    fn get_partition_sizes(simulator: &Simulator) -> Vec<(i32, Option<i32>)> {
+        simulator
+            .memory
+            .partitions
+            .iter()
+            .map(|partition| (partition.size, partition.process_id))
+            .collect()
+    }
+
+    #[test]
+    fn without_a_table_the_whole_memory_is_a_single_partition() {
+        let mut simulator = create_simulator(Vec::new(), &[30, 20]);
+        simulator.step();
+        assert_eq!(get_partition_sizes(&simulator), vec![(50, Some(1))]);
+
+        simulator.run();
+        assert_eq!(simulator.take_log().finished_processes.len(), 2);
+    }
+
+    #[test]
+    fn a_process_can_enter_the_space_left_after_the_table() {
+        let mut simulator = create_simulator(vec![10], &[30]);
+        simulator.step();
+        assert_eq!(
+            get_partition_sizes(&simulator),
+            vec![(10, None), (40, Some(1))]
+        );
+
+        simulator.run();
+        assert_eq!(simulator.take_log().finished_processes.len(), 1);
+    }
+}
@@ -0,0 +1,153 @@
+use crate::database::models;
+
+use super::Simulator;
+
+impl Simulator {
+    /// Measures how fragmented the memory is. A hole is all the free memory
+    /// that is next to each other, be it empty partitions or free frames.
+    pub(super) fn create_fragmentation_log(&mut self) {
+        let (memory_size, holes) = self.get_holes();
+        let free_memory: i32 = holes.iter().sum();
+        let largest_hole = holes.iter().copied().max().unwrap_or(0);
+        let internal_fragmentation: i32 = (0..self.memory.partitions.len())
+            .map(|i| self.get_internal_fragmentation(i))
+            .sum();
+        // The memory actually used by the processes.
+        let utilization = if memory_size > 0 {
+            (memory_size - free_memory - internal_fragmentation) as f64 / memory_size as f64
+        } else {
+            0.0
+        };
+
+        self.log.fragmentation_logs.push(models::FragmentationLog {
+            iteration: self.iteration,
+            free_memory,
+            largest_hole,
+            holes: holes.len() as i32,
+            external_fragmentation: get_external_fragmentation(&holes),
+            utilization,
+        });
+    }
+
+    /// How much of the free memory is not in the largest hole, from 0 to 1.
+    pub(super) fn get_external_fragmentation(&self) -> f64 {
+        get_external_fragmentation(&self.get_holes().1)
+    }
+
+    /// Returns the size of the memory and the sizes of its holes in order.
+    fn get_holes(&self) -> (i32, Vec<i32>) {
+        // Every part of the memory in order, with its size and if it is free.
+        let (memory_size, parts): (i32, Vec<(i32, bool)>) = if self.is_paged() {
+            (
+                self.memory.frames.len() as i32 * self.page_size,
+                self.memory
+                    .frames
+                    .iter()
+                    .map(|frame| (self.page_size, frame.process_id.is_none()))
+                    .collect(),
+            )
+        } else {
+            let mut parts: Vec<(i32, bool)> = self
+                .memory
+                .partitions
+                .iter()
+                .map(|partition| (partition.size, partition.process_id.is_none()))
+                .collect();
+            parts.push((self.memory.get_remaining_space(), true));
+            (self.memory.size, parts)
+        };
+
+        let mut holes = Vec::new();
+        let mut hole = 0;
+        for (size, is_free) in parts {
+            if is_free {
+                hole += size;
+            } else if hole > 0 {
+                holes.push(hole);
+                hole = 0;
+            }
+        }
+        if hole > 0 {
+            holes.push(hole);
+        }
+        (memory_size, holes)
+    }
+}
+
+/// The free memory that can't be used by a process as big as all of it,
+/// because it is not in the largest hole.
+fn get_external_fragmentation(holes: &[i32]) -> f64 {
+    let free_memory: i32 = holes.iter().sum();
+    let largest_hole = holes.iter().copied().max().unwrap_or(0);
+    if free_memory > 0 {
+        1.0 - largest_hole as f64 / free_memory as f64
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::allocation::AllocationEnum;
+    use crate::model::compaction::CompactionEnum;
+    use crate::model::process::Process;
+    use crate::model::simulator::SimulatorSettings;
+
+    fn get_fragmentation(simulator: &mut Simulator) -> Vec<(i32, i32, i32, f64, f64)> {
+        simulator.run();
+        simulator
+            .take_log()
+            .fragmentation_logs
+            .iter()
+            .map(|log| {
+                (
+                    log.free_memory,
+                    log.largest_hole,
+                    log.holes,
+                    log.external_fragmentation,
+                    log.utilization,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn the_free_memory_outside_the_largest_hole_is_fragmented() {
+        assert_eq!(get_external_fragmentation(&[]), 0.0);
+        assert_eq!(get_external_fragmentation(&[20]), 0.0);
+        assert_eq!(get_external_fragmentation(&[10, 30]), 0.25);
+    }
+
+    #[test]
+    fn the_holes_left_by_the_processes_are_measured() {
+        let settings = SimulatorSettings {
+            compaction_policy: CompactionEnum::Never as i32,
+            ..SimulatorSettings::default()
+        };
+        let processes = vec![
+            Process::new("P1".to_string(), 1, 10),
+            Process::new("P2".to_string(), 5, 10),
+            Process::new("P3".to_string(), 1, 10),
+        ];
+        let fragmentation = get_fragmentation(&mut Simulator::new(&settings, processes));
+
+        // P1 and P3 leave P2 between a hole of 10 and one of 30.
+        assert_eq!(fragmentation[1], (20, 20, 1, 0.0, 0.6));
+        assert_eq!(fragmentation[2], (40, 30, 2, 0.25, 0.2));
+        assert_eq!(fragmentation.last(), Some(&(50, 50, 1, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn the_unused_space_of_a_partition_is_not_utilized() {
+        let settings = SimulatorSettings {
+            allocation_mode: AllocationEnum::FixedPartitions as i32,
+            fixed_partition_sizes: vec![20, 30],
+            ..SimulatorSettings::default()
+        };
+        let processes = vec![Process::new("P1".to_string(), 1, 10)];
+        let fragmentation = get_fragmentation(&mut Simulator::new(&settings, processes));
+
+        assert_eq!(fragmentation[1], (30, 30, 1, 0.0, 0.2));
+    }
+}
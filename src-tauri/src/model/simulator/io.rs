@@ -0,0 +1,125 @@
+use crate::model::state::{get_state_from_enum, StateEnum};
+
+use super::Simulator;
+
+impl Simulator {
+    /// The blocked process waits for an I/O device, it keeps its memory while
+    /// it is blocked.
+    pub(super) fn block_process(&mut self, process_id: i32) {
+        let process = self
+            .processes
+            .iter()
+            .find(|process| process.id == Some(process_id))
+            .unwrap();
+        let duration = process
+            .io_bursts
+            .iter()
+            .find(|burst| burst.start == process.cpu_time)
+            .map(|burst| burst.duration)
+            .unwrap_or(1);
+        self.io_queue.push(process_id);
+        self.io_times.insert(process_id, duration);
+    }
+
+    /// The first blocked processes in the queue get an I/O device, a process
+    /// that finishes its I/O goes back to wait for a CPU in its partition.
+    pub(super) fn advance_io(&mut self) {
+        let devices = std::cmp::min(self.io_devices as usize, self.io_queue.len());
+        let process_ids: Vec<i32> = self.io_queue[..devices].to_vec();
+        for process_id in process_ids {
+            let time = self.io_times.get_mut(&process_id).unwrap();
+            *time -= 1;
+            if *time <= 0 {
+                self.io_times.remove(&process_id);
+                self.io_queue.retain(|&id| id != process_id);
+                let process = self
+                    .processes
+                    .iter_mut()
+                    .find(|process| process.id == Some(process_id))
+                    .unwrap();
+                process.state = get_state_from_enum(StateEnum::ReadyInPartition as i32);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::process::{IoBurst, Process};
+    use crate::model::simulator::SimulatorSettings;
+
+    fn create_simulator(io_devices: i32) -> Simulator {
+        let settings = SimulatorSettings {
+            io_devices,
+            ..SimulatorSettings::default()
+        };
+        let processes = ["P1", "P2"]
+            .iter()
+            .map(|name| {
+                let mut process = Process::new(name.to_string(), 3, 10);
+                process
+                    .set_io_bursts(vec![IoBurst {
+                        start: 1,
+                        duration: 2,
+                    }])
+                    .unwrap();
+                process
+            })
+            .collect();
+        Simulator::new(&settings, processes)
+    }
+
+    fn get_finish_iterations(simulator: &mut Simulator) -> Vec<(i32, i32)> {
+        simulator.run();
+        simulator
+            .take_log()
+            .finished_processes
+            .iter()
+            .map(|process| (process.process_id, process.finish_iteration))
+            .collect()
+    }
+
+    #[test]
+    fn the_blocked_processes_keep_their_partition() {
+        let mut simulator = create_simulator(1);
+        simulator.run();
+        let blocked: Vec<(i32, i32, i32)> = simulator
+            .take_log()
+            .process_logs
+            .iter()
+            .filter(|process_log| process_log.state == StateEnum::Blocked as i32)
+            .map(|process_log| {
+                (
+                    process_log.iteration,
+                    process_log.process_id,
+                    process_log.storage_partition_id,
+                )
+            })
+            .collect();
+        // P2 waits for the device while P1 does its I/O.
+        assert_eq!(
+            blocked,
+            vec![
+                (2, 1, 1),
+                (2, 2, 2),
+                (3, 1, 1),
+                (3, 2, 2),
+                (4, 2, 2),
+                (5, 2, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn the_processes_wait_for_a_free_device() {
+        assert_eq!(
+            get_finish_iterations(&mut create_simulator(1)),
+            vec![(1, 7), (2, 9)]
+        );
+        assert_eq!(
+            get_finish_iterations(&mut create_simulator(2)),
+            vec![(1, 7), (2, 7)]
+        );
+    }
+}
@@ -0,0 +1,126 @@
+use crate::database::models;
+use crate::model::memory::Frame;
+
+use super::Simulator;
+
+impl Simulator {
+    /// Splits the memory in frames the size of a page, the space left over at
+    /// the end is not used.
+    pub(super) fn create_frames(&mut self) {
+        for number in 0..self.memory.size / self.page_size {
+            self.memory.frames.push(Frame::new(number));
+        }
+    }
+
+    /// Loads every page of the process in the free frames, the frames don't
+    /// need to be next to each other. Returns the frame with the first page,
+    /// or none if there are not enough free frames.
+    pub(super) fn allocate_frames(&mut self, process_id: i32, process_size: i32) -> Option<i32> {
+        let page_count = get_page_count(process_size, self.page_size) as usize;
+        let free_frames: Vec<usize> = (0..self.memory.frames.len())
+            .filter(|&i| self.memory.frames[i].process_id.is_none())
+            .take(page_count)
+            .collect();
+        if free_frames.len() < page_count {
+            return None;
+        }
+
+        for (page, &i) in free_frames.iter().enumerate() {
+            self.memory.frames[i].process_id = Some(process_id);
+            self.memory.frames[i].page = Some(page as i32);
+        }
+        Some(self.memory.frames[free_frames[0]].number)
+    }
+
+    /// The frame every page of the resident processes is in.
+    pub fn get_page_tables(&self) -> Vec<models::PageTable> {
+        let mut page_tables: Vec<models::PageTable> = self
+            .memory
+            .frames
+            .iter()
+            .filter_map(|frame| match (frame.process_id, frame.page) {
+                (Some(process_id), Some(page)) => Some(models::PageTable {
+                    process_id,
+                    page,
+                    frame: frame.number,
+                }),
+                _ => None,
+            })
+            .collect();
+        page_tables.sort_by_key(|page_table| (page_table.process_id, page_table.page));
+        page_tables
+    }
+
+    pub(super) fn create_frame_logs(&mut self) {
+        for frame in &self.memory.frames {
+            self.log.frame_logs.push(models::NewFrameLog {
+                iteration: self.iteration,
+                frame: frame.number,
+                process_id: frame.process_id,
+                page: frame.page,
+            });
+        }
+    }
+}
+
+/// Every process takes at least one page, the last page may not be full.
+pub fn get_page_count(process_size: i32, page_size: i32) -> i32 {
+    ((process_size + page_size - 1) / page_size).max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::allocation::AllocationEnum;
+    use crate::model::process::Process;
+    use crate::model::simulator::SimulatorSettings;
+
+    fn get_frames(simulator: &Simulator, process_id: i32) -> Vec<i32> {
+        simulator
+            .get_page_tables()
+            .iter()
+            .filter(|page_table| page_table.process_id == process_id)
+            .map(|page_table| page_table.frame)
+            .collect()
+    }
+
+    #[test]
+    fn the_last_page_may_not_be_full() {
+        assert_eq!(get_page_count(8, 4), 2);
+        assert_eq!(get_page_count(9, 4), 3);
+        assert_eq!(get_page_count(0, 4), 1);
+    }
+
+    #[test]
+    fn the_pages_are_loaded_in_any_free_frame() {
+        let settings = SimulatorSettings {
+            allocation_mode: AllocationEnum::Paging as i32,
+            page_size: 4,
+            ..SimulatorSettings::default()
+        };
+        let processes = vec![
+            Process::new("P1".to_string(), 1, 10),
+            Process::new("P2".to_string(), 3, 9),
+            Process::new("P3".to_string(), 1, 30),
+        ];
+        let mut simulator = Simulator::new(&settings, processes);
+
+        // The memory has 12 frames, P3 needs 8 and only 6 are free.
+        simulator.step();
+        assert_eq!(simulator.memory.frames.len(), 12);
+        assert_eq!(get_frames(&simulator, 1), vec![0, 1, 2]);
+        assert_eq!(get_frames(&simulator, 2), vec![3, 4, 5]);
+        assert!(get_frames(&simulator, 3).is_empty());
+
+        // P3 enters the frames P1 leaves and the ones after P2.
+        while get_frames(&simulator, 3).is_empty() {
+            assert!(!simulator.step());
+        }
+        assert!(get_frames(&simulator, 1).is_empty());
+        assert_eq!(get_frames(&simulator, 3), vec![0, 1, 2, 6, 7, 8, 9, 10]);
+
+        simulator.run();
+        assert!(simulator.get_page_tables().is_empty());
+        assert_eq!(simulator.take_log().finished_processes.len(), 3);
+    }
+}
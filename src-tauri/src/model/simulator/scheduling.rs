@@ -0,0 +1,96 @@
+use crate::model::scheduling::SchedulableProcess;
+use crate::model::state::StateEnum;
+
+use super::Simulator;
+
+impl Simulator {
+    /// Gives the CPUs to the resident processes that can run and returns the
+    /// ids of the ones that got a CPU. The processes wait in the ready queue in
+    /// the order they entered it, a process that loses its CPU goes back to the
+    /// end of the queue.
+    pub(super) fn schedule_processes(&mut self) -> Vec<i32> {
+        // The processes being swapped can't run, even if they are in memory.
+        let schedulable_processes: Vec<i32> = self
+            .processes
+            .iter()
+            .filter(|process| {
+                let state = process.state.as_ref().unwrap().get_state_number();
+                (state == StateEnum::Ready as i32
+                    || state == StateEnum::ReadyInPartition as i32
+                    || state == StateEnum::Running as i32)
+                    && self
+                        .memory
+                        .find_storage_with_process_id(process.id.unwrap())
+                        .is_some()
+            })
+            .map(|process| process.id.unwrap())
+            .collect();
+        self.ready_queue
+            .retain(|process_id| schedulable_processes.contains(process_id));
+        for process_id in schedulable_processes {
+            if !self.ready_queue.contains(&process_id) {
+                self.ready_queue.push(process_id);
+            }
+        }
+
+        let queued_processes: Vec<SchedulableProcess> = self
+            .ready_queue
+            .iter()
+            .map(|&process_id| {
+                let process = self
+                    .processes
+                    .iter()
+                    .find(|process| process.id == Some(process_id))
+                    .unwrap();
+                SchedulableProcess {
+                    time: process.time,
+                    priority: self.get_effective_priority(process),
+                    is_running: process.state.as_ref().unwrap().get_state_number()
+                        == StateEnum::Running as i32,
+                    quantum_used: *self.quantum_used.get(&process_id).unwrap_or(&0),
+                }
+            })
+            .collect();
+        // Without a limit of CPUs every process in the queue gets one.
+        let scheduled_processes: Vec<i32> = if self.cpus == 0 {
+            self.ready_queue.clone()
+        } else {
+            self.scheduler
+                .select_processes(&queued_processes, self.cpus as usize)
+                .iter()
+                .map(|&i| self.ready_queue[i])
+                .collect()
+        };
+
+        let preempted_processes: Vec<i32> = (0..queued_processes.len())
+            .filter(|&i| {
+                queued_processes[i].is_running
+                    && !scheduled_processes.contains(&self.ready_queue[i])
+            })
+            .map(|i| self.ready_queue[i])
+            .collect();
+        for process_id in preempted_processes {
+            self.ready_queue.retain(|&id| id != process_id);
+            self.ready_queue.push(process_id);
+        }
+
+        // The quantum only counts the iterations in a row a process has run,
+        // and the processes that didn't run keep waiting.
+        self.quantum_used
+            .retain(|process_id, _| scheduled_processes.contains(process_id));
+        for &process_id in &scheduled_processes {
+            *self.quantum_used.entry(process_id).or_insert(0) += 1;
+            self.waiting_times.remove(&process_id);
+        }
+        let waiting_processes: Vec<i32> = self
+            .ready_queue
+            .iter()
+            .copied()
+            .filter(|process_id| !scheduled_processes.contains(process_id))
+            .collect();
+        for process_id in waiting_processes {
+            self.add_waiting_time(process_id);
+        }
+        scheduled_processes
+    }
+}
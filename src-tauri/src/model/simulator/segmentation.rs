@@ -0,0 +1,121 @@
+use crate::database::models;
+
+use super::Simulator;
+
+impl Simulator {
+    /// Places every segment of the process in its own partition, the same way
+    /// a whole process is placed with dynamic partitions. Returns the partition
+    /// of the first segment, or none if any of the segments doesn't fit.
+    pub(super) fn allocate_segments(&mut self, process_id: i32) -> Option<i32> {
+        let segments = self
+            .processes
+            .iter()
+            .find(|process| process.id == Some(process_id))?
+            .get_segments();
+
+        // The process can only run with all of its segments in memory, so if
+        // one doesn't fit the memory is left as it was before placing any.
+        let partitions = self.memory.partitions.clone();
+        let events_count = self.events.len();
+        let next_fit_position = self.next_fit_position;
+        let partition_consecutive_number = self.partition_consecutive_number;
+
+        let mut first_partition_number = None;
+        for (number, segment) in segments.iter().enumerate() {
+            let position = self
+                .get_empty_partition(segment.size)
+                .or_else(|| self.create_partition(segment.size));
+            match position {
+                Some(position) => {
+                    let partition = &mut self.memory.partitions[position];
+                    partition.process_id = Some(process_id);
+                    partition.segment = Some(number as i32);
+                    first_partition_number.get_or_insert(partition.number);
+                }
+                None => {
+                    self.memory.partitions = partitions;
+                    self.events.truncate(events_count);
+                    self.next_fit_position = next_fit_position;
+                    self.partition_consecutive_number = partition_consecutive_number;
+                    return None;
+                }
+            }
+        }
+        first_partition_number
+    }
+
+    /// Where every segment of the resident processes starts and how big it is.
+    pub fn get_segment_tables(&self) -> Vec<models::SegmentTable> {
+        let mut segment_tables = Vec::new();
+        for i in 0..self.memory.partitions.len() {
+            let partition = &self.memory.partitions[i];
+            if let (Some(process_id), Some(segment)) = (partition.process_id, partition.segment) {
+                let process = self
+                    .processes
+                    .iter()
+                    .find(|process| process.id == Some(process_id))
+                    .unwrap();
+                let segment_size = process.get_segments()[segment as usize].size;
+                segment_tables.push(models::SegmentTable {
+                    process_id,
+                    segment,
+                    storage_partition_id: partition.number,
+                    base: self.memory.get_position_start(i),
+                    size: segment_size,
+                });
+            }
+        }
+        segment_tables
+            .sort_by_key(|segment_table| (segment_table.process_id, segment_table.segment));
+        segment_tables
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::allocation::AllocationEnum;
+    use crate::model::process::{Process, Segment};
+    use crate::model::simulator::SimulatorSettings;
+
+    #[test]
+    fn the_memory_is_not_changed_when_a_segment_does_not_fit() {
+        let settings = SimulatorSettings {
+            allocation_mode: AllocationEnum::Segmentation as i32,
+            ..SimulatorSettings::default()
+        };
+        let mut process = Process::new("P2".to_string(), 1, 0);
+        process
+            .set_segments(vec![
+                Segment {
+                    name: "code".to_string(),
+                    size: 15,
+                },
+                Segment {
+                    name: "data".to_string(),
+                    size: 10,
+                },
+            ])
+            .unwrap();
+        let processes = vec![Process::new("P1".to_string(), 5, 30), process];
+        let mut simulator = Simulator::new(&settings, processes);
+        simulator.step();
+        let partition_consecutive_number = simulator.partition_consecutive_number;
+        simulator.take_events();
+
+        assert_eq!(simulator.allocate_segments(2), None);
+        let partitions: Vec<(i32, Option<i32>)> = simulator
+            .memory
+            .partitions
+            .iter()
+            .map(|partition| (partition.size, partition.process_id))
+            .collect();
+        assert_eq!(partitions, vec![(30, Some(1)), (20, None)]);
+        assert_eq!(simulator.next_fit_position, 0);
+        assert_eq!(
+            simulator.partition_consecutive_number,
+            partition_consecutive_number
+        );
+        assert!(simulator.take_events().is_empty());
+    }
+}
@@ -0,0 +1,166 @@
+use crate::database::models;
+use crate::model::event::SimulatorEvent;
+use crate::model::state::{get_state_from_enum, StateEnum};
+
+use super::Simulator;
+
+impl Simulator {
+    /// Moves the swaps in progress forward, a process that finishes swapping
+    /// out leaves the memory and one that finishes swapping in can run again.
+    pub(super) fn advance_swaps(&mut self) {
+        let mut process_ids: Vec<i32> = self.swap_times.keys().copied().collect();
+        process_ids.sort();
+        for process_id in process_ids {
+            let time = self.swap_times.get_mut(&process_id).unwrap();
+            *time -= 1;
+            if *time <= 0 {
+                self.swap_times.remove(&process_id);
+                self.finish_swap(process_id);
+            }
+        }
+    }
+
+    /// Starts swapping out a resident process so a process of the given size
+    /// can enter. Only one process is swapped out at a time, and it is the one
+    /// with the most time left among the ones that have already run and hold
+    /// enough memory.
+    pub(super) fn swap_out_for(&mut self, process_size: i32) {
+        if self.processes.iter().any(|process| {
+            process.state.as_ref().unwrap().get_state_number() == StateEnum::SwappingOut as i32
+        }) {
+            return;
+        }
+
+        let victim = self
+            .processes
+            .iter()
+            .filter(|process| {
+                let state = process.state.as_ref().unwrap().get_state_number();
+                (state == StateEnum::Running as i32 || state == StateEnum::ReadyInPartition as i32)
+                    && self.get_held_memory(process.id.unwrap()) >= process_size
+            })
+            .max_by_key(|process| process.time)
+            .map(|process| process.id.unwrap());
+
+        if let Some(process_id) = victim {
+            self.start_swap(process_id, StateEnum::SwappingOut);
+        }
+    }
+
+    /// The process stays in memory while it is being swapped, the swap
+    /// finishes when the swap time has passed.
+    pub(super) fn start_swap(&mut self, process_id: i32, state: StateEnum) {
+        self.set_swap_state(process_id, state);
+        self.swap_times.insert(process_id, self.swap_time);
+    }
+
+    fn finish_swap(&mut self, process_id: i32) {
+        let process = self
+            .processes
+            .iter()
+            .find(|process| process.id == Some(process_id))
+            .unwrap();
+        if process.state.as_ref().unwrap().get_state_number() == StateEnum::SwappingOut as i32 {
+            // The swap log keeps the partition the process left.
+            self.set_swap_state(process_id, StateEnum::Swapped);
+            self.memory.free_process(process_id);
+        } else {
+            self.set_swap_state(process_id, StateEnum::ReadyInPartition);
+        }
+    }
+
+    fn set_swap_state(&mut self, process_id: i32, state: StateEnum) {
+        let storage_partition_id = self
+            .memory
+            .find_storage_with_process_id(process_id)
+            .unwrap_or(-1);
+        let process = self
+            .processes
+            .iter_mut()
+            .find(|process| process.id == Some(process_id))
+            .unwrap();
+        process.state = get_state_from_enum(state as i32);
+
+        self.last_swap_log_id += 1;
+        let swap_log = models::SwapLog {
+            id: self.last_swap_log_id,
+            iteration: self.iteration,
+            process_id,
+            storage_partition_id,
+            state: state as i32,
+        };
+        self.events.push(SimulatorEvent::Swap(swap_log.clone()));
+        self.log.swap_logs.push(swap_log);
+    }
+
+    /// All the memory the process is in, be it partitions or frames.
+    fn get_held_memory(&self, process_id: i32) -> i32 {
+        let partitions_size: i32 = self
+            .memory
+            .partitions
+            .iter()
+            .filter(|partition| partition.process_id == Some(process_id))
+            .map(|partition| partition.size)
+            .sum();
+        let frames_count = self
+            .memory
+            .frames
+            .iter()
+            .filter(|frame| frame.process_id == Some(process_id))
+            .count() as i32;
+        partitions_size + frames_count * self.page_size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::process::Process;
+    use crate::model::simulator::SimulatorSettings;
+
+    fn create_settings(swap_time: i32) -> SimulatorSettings {
+        SimulatorSettings {
+            swapping: true,
+            swap_time,
+            ..SimulatorSettings::default()
+        }
+    }
+
+    #[test]
+    fn a_process_is_swapped_out_for_a_new_one_and_swapped_in_after_it() {
+        let processes = vec![
+            Process::new("P1".to_string(), 4, 40),
+            Process::new("P2".to_string(), 1, 30),
+        ];
+        let mut simulator = Simulator::new(&create_settings(1), processes);
+        simulator.run();
+        let log = simulator.take_log();
+
+        let swaps: Vec<(i32, i32, i32)> = log
+            .swap_logs
+            .iter()
+            .map(|swap_log| (swap_log.iteration, swap_log.process_id, swap_log.state))
+            .collect();
+        assert_eq!(
+            swaps,
+            vec![
+                (2, 1, StateEnum::SwappingOut as i32),
+                (3, 1, StateEnum::Swapped as i32),
+                (5, 1, StateEnum::SwappingIn as i32),
+                (6, 1, StateEnum::ReadyInPartition as i32),
+            ]
+        );
+        let finished: Vec<(i32, i32)> = log
+            .finished_processes
+            .iter()
+            .map(|process| (process.process_id, process.finish_iteration))
+            .collect();
+        assert_eq!(finished, vec![(2, 5), (1, 12)]);
+    }
+
+    #[test]
+    fn swapping_has_to_take_at_least_one_iteration() {
+        assert!(create_settings(1).validate().is_ok());
+        assert!(create_settings(0).validate().is_err());
+    }
+}
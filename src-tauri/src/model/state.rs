@@ -6,11 +6,44 @@ pub enum StateEnum {
     ReadyInPartition,
     Running,
     Finished,
+    SwappingOut,
+    Swapped,
+    SwappingIn,
+    Blocked,
 }
 
 const PROCESSING_TIME: i32 = 1;
 
-pub trait State {
+/// The process uses its CPU for an iteration and is blocked when it has to do
+/// I/O.
+fn use_cpu(process: &mut Process) -> Box<dyn State> {
+    process.time = std::cmp::max(0, process.time - PROCESSING_TIME);
+    process.cpu_time += PROCESSING_TIME;
+    if process.time > 0
+        && process
+            .io_bursts
+            .iter()
+            .any(|burst| burst.start == process.cpu_time)
+    {
+        Box::new(Blocked {})
+    } else {
+        Box::new(Running {})
+    }
+}
+
+/// The process keeps its CPU while the scheduler lets it, so it finishes in
+/// the same iteration it uses its last time.
+pub fn run(process: &mut Process) -> Box<dyn State> {
+    let state = use_cpu(process);
+    if process.time <= 0 {
+        // The simulator takes the finished process out of its partition.
+        Box::new(Finished {})
+    } else {
+        state
+    }
+}
+
+pub trait State: Send {
     fn process(self: Box<Self>, process: &mut Process) -> Box<dyn State>;
     fn get_state_number(&self) -> i32;
 }
@@ -18,8 +51,7 @@ pub trait State {
 pub struct Ready;
 impl State for Ready {
     fn process(self: Box<Ready>, process: &mut Process) -> Box<dyn State> {
-        process.time = std::cmp::max(0, process.time - PROCESSING_TIME);
-        Box::new(Running {})
+        use_cpu(process)
     }
 
     fn get_state_number(&self) -> i32 {
@@ -30,8 +62,7 @@ impl State for Ready {
 struct ReadyInPartition;
 impl State for ReadyInPartition {
     fn process(self: Box<Self>, process: &mut Process) -> Box<dyn State> {
-        process.time = std::cmp::max(0, process.time - PROCESSING_TIME);
-        Box::new(Running {})
+        use_cpu(process)
     }
 
     fn get_state_number(&self) -> i32 {
@@ -42,21 +73,10 @@ impl State for ReadyInPartition {
 struct Running;
 impl State for Running {
     fn process(self: Box<Running>, process: &mut Process) -> Box<dyn State> {
-        use crate::database;
         if process.time > 0 {
             Box::new(ReadyInPartition {})
         } else {
-            // Add the finished process to the finished process list with the
-            // information of the partition the process was in.
-            database::create_finished_process(
-                process.id.unwrap(),
-                database::select_storage_partition_with_process_id(process.id.unwrap())
-                    .0
-                    .number,
-            );
-            // Remove the process from the processor, this is the process
-            // partition.
-            database::delete_process_partition_with_process_id(process.id.unwrap());
+            // The simulator takes the finished process out of its partition.
             Box::new(Finished {})
         }
     }
@@ -77,12 +97,80 @@ impl State for Finished {
     }
 }
 
+// The swapping states don't change by themselves, the simulator moves the
+// process out of them when the swap has taken all of its time.
+struct SwappingOut;
+impl State for SwappingOut {
+    fn process(self: Box<Self>, _process: &mut Process) -> Box<dyn State> {
+        Box::new(Self)
+    }
+
+    fn get_state_number(&self) -> i32 {
+        StateEnum::SwappingOut as i32
+    }
+}
+
+struct Swapped;
+impl State for Swapped {
+    fn process(self: Box<Self>, _process: &mut Process) -> Box<dyn State> {
+        Box::new(Self)
+    }
+
+    fn get_state_number(&self) -> i32 {
+        StateEnum::Swapped as i32
+    }
+}
+
+struct SwappingIn;
+impl State for SwappingIn {
+    fn process(self: Box<Self>, _process: &mut Process) -> Box<dyn State> {
+        Box::new(Self)
+    }
+
+    fn get_state_number(&self) -> i32 {
+        StateEnum::SwappingIn as i32
+    }
+}
+
+// The blocked process doesn't change by itself either, the simulator moves it
+// out when its I/O has finished.
+struct Blocked;
+impl State for Blocked {
+    fn process(self: Box<Self>, _process: &mut Process) -> Box<dyn State> {
+        Box::new(Self)
+    }
+
+    fn get_state_number(&self) -> i32 {
+        StateEnum::Blocked as i32
+    }
+}
+
+/// The states are saved as their numbers, so a number that is not one of them
+/// means the value was not saved by the app.
+pub fn get_state_name(value: i32) -> Option<&'static str> {
+    match value {
+        value if value == StateEnum::Ready as i32 => Some("Ready"),
+        value if value == StateEnum::ReadyInPartition as i32 => Some("Ready in partition"),
+        value if value == StateEnum::Running as i32 => Some("Running"),
+        value if value == StateEnum::Finished as i32 => Some("Finished"),
+        value if value == StateEnum::SwappingOut as i32 => Some("Swapping out"),
+        value if value == StateEnum::Swapped as i32 => Some("Swapped"),
+        value if value == StateEnum::SwappingIn as i32 => Some("Swapping in"),
+        value if value == StateEnum::Blocked as i32 => Some("Blocked"),
+        _ => None,
+    }
+}
+
 pub fn get_state_from_enum(value: i32) -> Option<Box<dyn State>> {
     match value {
         value if value == StateEnum::Ready as i32 => Some(Box::new(Ready {})),
         value if value == StateEnum::ReadyInPartition as i32 => Some(Box::new(ReadyInPartition {})),
         value if value == StateEnum::Running as i32 => Some(Box::new(Running {})),
         value if value == StateEnum::Finished as i32 => Some(Box::new(Finished {})),
-        _ => panic!("State not recognized"),
+        value if value == StateEnum::SwappingOut as i32 => Some(Box::new(SwappingOut {})),
+        value if value == StateEnum::Swapped as i32 => Some(Box::new(Swapped {})),
+        value if value == StateEnum::SwappingIn as i32 => Some(Box::new(SwappingIn {})),
+        value if value == StateEnum::Blocked as i32 => Some(Box::new(Blocked {})),
+        _ => None,
     }
 }
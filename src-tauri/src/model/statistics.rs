@@ -0,0 +1,126 @@
+use serde::Serialize;
+
+use crate::database::models;
+
+/// The times of a finished process, in iterations. A process that arrives in an
+/// iteration can run from the next one on.
+#[derive(Serialize)]
+pub struct ProcessStatistics {
+    pub process_id: i32,
+    pub name: String,
+    pub arrival: i32,
+    pub admission_iteration: i32,
+    pub first_run_iteration: i32,
+    pub finish_iteration: i32,
+    /// From the arrival of the process until it finished.
+    pub turnaround_time: i32,
+    /// The time the process was waiting to enter the memory or to get a CPU.
+    pub waiting_time: i32,
+    /// From the arrival of the process until it first ran.
+    pub response_time: i32,
+}
+
+#[derive(Serialize)]
+pub struct SimulationStatistics {
+    pub processes: Vec<ProcessStatistics>,
+    pub average_turnaround_time: f64,
+    pub average_waiting_time: f64,
+    pub average_response_time: f64,
+}
+
+impl ProcessStatistics {
+    pub fn new(
+        process_id: i32,
+        name: String,
+        arrival: i32,
+        admission_iteration: i32,
+        first_run_iteration: i32,
+        finish_iteration: i32,
+        waiting_time: i32,
+    ) -> ProcessStatistics {
+        ProcessStatistics {
+            process_id,
+            name,
+            arrival,
+            admission_iteration,
+            first_run_iteration,
+            finish_iteration,
+            turnaround_time: finish_iteration - arrival,
+            waiting_time,
+            response_time: first_run_iteration - arrival - 1,
+        }
+    }
+}
+
+pub fn get_simulation_statistics(
+    finished_processes: &[(models::FinishedProcess, models::Process)],
+) -> SimulationStatistics {
+    create_simulation_statistics(
+        finished_processes
+            .iter()
+            .map(|(finished_process, process)| {
+                ProcessStatistics::new(
+                    process.id,
+                    process.name.to_string(),
+                    process.arrival,
+                    finished_process.admission_iteration,
+                    finished_process.first_run_iteration,
+                    finished_process.finish_iteration,
+                    finished_process.waiting_time,
+                )
+            })
+            .collect(),
+    )
+}
+
+pub fn create_simulation_statistics(processes: Vec<ProcessStatistics>) -> SimulationStatistics {
+    SimulationStatistics {
+        average_turnaround_time: get_average(&processes, |process| process.turnaround_time),
+        average_waiting_time: get_average(&processes, |process| process.waiting_time),
+        average_response_time: get_average(&processes, |process| process.response_time),
+        processes,
+    }
+}
+
+fn get_average(
+    processes: &[ProcessStatistics],
+    time: impl Fn(&ProcessStatistics) -> i32,
+) -> f64 {
+    if processes.is_empty() {
+        return 0.0;
+    }
+    processes.iter().map(time).sum::<i32>() as f64 / processes.len() as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_times_are_counted_from_the_arrival() {
+        let process = ProcessStatistics::new(1, "P1".to_string(), 2, 3, 4, 10, 3);
+        assert_eq!(process.turnaround_time, 8);
+        assert_eq!(process.waiting_time, 3);
+        assert_eq!(process.response_time, 1);
+    }
+
+    #[test]
+    fn the_averages_are_the_ones_of_every_process() {
+        let statistics = create_simulation_statistics(vec![
+            ProcessStatistics::new(1, "P1".to_string(), 1, 1, 2, 5, 0),
+            ProcessStatistics::new(2, "P2".to_string(), 1, 2, 4, 8, 3),
+        ]);
+        assert_eq!(statistics.processes.len(), 2);
+        assert_eq!(statistics.average_turnaround_time, 5.5);
+        assert_eq!(statistics.average_waiting_time, 1.5);
+        assert_eq!(statistics.average_response_time, 1.0);
+    }
+
+    #[test]
+    fn there_are_no_averages_without_processes() {
+        let statistics = create_simulation_statistics(Vec::new());
+        assert_eq!(statistics.average_turnaround_time, 0.0);
+        assert_eq!(statistics.average_waiting_time, 0.0);
+        assert_eq!(statistics.average_response_time, 0.0);
+    }
+}
@@ -0,0 +1,156 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use crate::database;
+use crate::database::configuration;
+use crate::error::SimError;
+use crate::model::event::SimulatorEvent;
+use crate::model::simulator::Simulator;
+
+/// Keeps the simulator between the processor commands, so it can be run one
+/// iteration at a time and paused while it is running.
+#[derive(Default)]
+pub struct Processor {
+    simulator: Mutex<Option<Simulator>>,
+    is_paused: AtomicBool,
+}
+
+impl Processor {
+    /// Runs a new simulation to the end, the finished simulator is kept so the
+    /// step commands know it has finished.
+    pub fn start(
+        &self,
+        emit: impl Fn(SimulatorEvent) -> Result<(), SimError>,
+    ) -> Result<bool, SimError> {
+        let mut simulator = create_simulator()?;
+        loop {
+            let has_finished = simulator.step();
+            emit_events(&mut simulator, &emit)?;
+            if has_finished {
+                break;
+            }
+        }
+        save_simulator(&mut simulator)?;
+        *self.simulator.lock().unwrap() = Some(simulator);
+        Ok(true)
+    }
+
+    /// Runs a single iteration, starting a new simulation if there is none.
+    /// Returns true when the processor has finished.
+    pub fn step(
+        &self,
+        emit: impl Fn(SimulatorEvent) -> Result<(), SimError>,
+    ) -> Result<bool, SimError> {
+        let mut simulator = self.simulator.lock().unwrap();
+        if !get_or_create_simulator(&mut simulator)?.has_finished {
+            run_iteration(&mut simulator, &emit)?;
+        }
+        Ok(simulator.as_ref().unwrap().has_finished)
+    }
+
+    /// Runs iterations until the given iteration has been reached, the
+    /// processor has finished or it has been paused.
+    pub fn run_until(
+        &self,
+        iteration: i32,
+        emit: impl Fn(SimulatorEvent) -> Result<(), SimError>,
+    ) -> Result<bool, SimError> {
+        self.is_paused.store(false, Ordering::SeqCst);
+        self.run_while(|simulator| simulator.iteration < iteration, emit)
+    }
+
+    pub fn pause(&self) {
+        self.is_paused.store(true, Ordering::SeqCst);
+    }
+
+    /// Keeps running the processor until it finishes or is paused again.
+    pub fn resume(
+        &self,
+        emit: impl Fn(SimulatorEvent) -> Result<(), SimError>,
+    ) -> Result<bool, SimError> {
+        self.is_paused.store(false, Ordering::SeqCst);
+        self.run_while(|_| true, emit)
+    }
+
+    /// Forgets the current simulation, the next step starts a new one.
+    pub fn reset(&self) {
+        self.is_paused.store(false, Ordering::SeqCst);
+        *self.simulator.lock().unwrap() = None;
+    }
+
+    fn run_while(
+        &self,
+        keep_running: impl Fn(&Simulator) -> bool,
+        emit: impl Fn(SimulatorEvent) -> Result<(), SimError>,
+    ) -> Result<bool, SimError> {
+        loop {
+            // The lock is taken for every iteration, so a pause can get in
+            // between the iterations.
+            let mut simulator = self.simulator.lock().unwrap();
+            let current = get_or_create_simulator(&mut simulator)?;
+            if current.has_finished
+                || self.is_paused.load(Ordering::SeqCst)
+                || !keep_running(current)
+            {
+                return Ok(current.has_finished);
+            }
+            run_iteration(&mut simulator, &emit)?;
+        }
+    }
+}
+
+/// The settings that are not valid and the processes that don't fit in the
+/// memory are an error, as they would never run.
+pub fn create_simulator() -> Result<Simulator, SimError> {
+    // Start database base configuration
+    database::init_configuration()?;
+    database::clear_database()?;
+
+    let processes = database::select_workload()?;
+
+    let settings = configuration::get_simulator_settings()?;
+    settings.validate()?;
+    settings.check_processes(&processes)?;
+    Ok(Simulator::new(&settings, processes))
+}
+
+/// Saves everything the simulator has logged since the last save, and its
+/// current memory, all of it or nothing.
+pub fn save_simulator(simulator: &mut Simulator) -> Result<(), SimError> {
+    database::simulation::save_simulation(simulator)
+}
+
+/// Runs an iteration of the simulation and saves it. If it can't be saved the
+/// database is left at the previous iteration, so the simulation is forgotten
+/// and the next step starts a new one. The events are only sent once the
+/// iteration has been saved.
+fn run_iteration(
+    simulator: &mut Option<Simulator>,
+    emit: &impl Fn(SimulatorEvent) -> Result<(), SimError>,
+) -> Result<(), SimError> {
+    let current = simulator.as_mut().unwrap();
+    current.step();
+    if let Err(error) = save_simulator(current) {
+        *simulator = None;
+        return Err(error);
+    }
+    emit_events(current, emit)
+}
+
+/// The simulation being run, or a new one if there is none.
+fn get_or_create_simulator(simulator: &mut Option<Simulator>) -> Result<&mut Simulator, SimError> {
+    if simulator.is_none() {
+        *simulator = Some(create_simulator()?);
+    }
+    Ok(simulator.as_mut().unwrap())
+}
+
+fn emit_events(
+    simulator: &mut Simulator,
+    emit: &impl Fn(SimulatorEvent) -> Result<(), SimError>,
+) -> Result<(), SimError> {
+    for event in simulator.take_events() {
+        emit(event)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,4 @@
+export type SimError = {
+    kind: string;
+    message: string;
+};